@@ -0,0 +1,95 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::config;
+
+// named directories like vim marks, one "<key> <path>" per line in $XDG_DATA_HOME/zfile/bookmarks
+pub struct Bookmarks {
+    //sorted by key
+    entries: Vec<(char, PathBuf)>,
+    //None if there is no data directory, then nothing gets saved
+    file: Option<PathBuf>,
+}
+
+impl Bookmarks {
+    pub fn load() -> Self {
+        let file = config::data_path("bookmarks");
+        let mut entries: Vec<(char, PathBuf)> = file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|content| content.lines().filter_map(parse_line).collect())
+            .unwrap_or_default();
+        entries.sort_by_key(|(key, _)| *key);
+        Bookmarks { entries, file }
+    }
+
+    pub fn get(&self, key: char) -> Option<&PathBuf> {
+        self.entries
+            .iter()
+            .find(|(bookmark, _)| *bookmark == key)
+            .map(|(_, path)| path)
+    }
+
+    //add or replace a bookmark and save the file
+    pub fn set(&mut self, key: char, path: PathBuf) {
+        self.entries.retain(|(bookmark, _)| *bookmark != key);
+        self.entries.push((key, path));
+        self.entries.sort_by_key(|(key, _)| *key);
+        self.save().ok();
+    }
+
+    pub fn remove(&mut self, key: char) {
+        self.entries.retain(|(bookmark, _)| *bookmark != key);
+        self.save().ok();
+    }
+
+    //the bookmarks matching the query, the best match first
+    pub fn filter(&self, query: &str, matcher: &SkimMatcherV2) -> Vec<(char, PathBuf)> {
+        if query.is_empty() {
+            return self.entries.clone();
+        }
+        let mut scored: Vec<(i64, (char, PathBuf))> = self
+            .entries
+            .iter()
+            .filter_map(|(key, path)| {
+                let text = format!("{} {}", key, path.to_string_lossy());
+                matcher
+                    .fuzzy_match(&text, query)
+                    .map(|score| (score, (*key, path.clone())))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, bookmark)| bookmark).collect()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content: String = self
+            .entries
+            .iter()
+            .map(|(key, path)| format!("{} {}\n", key, path.to_string_lossy()))
+            .collect();
+        fs::write(file, content)
+    }
+}
+
+// "<key> <path>", also used when a bookmark gets edited in the popup
+pub fn parse_line(line: &str) -> Option<(char, PathBuf)> {
+    let mut chars = line.trim_start().chars();
+    let key = chars.next()?;
+    let path = chars.as_str().trim();
+    if path.is_empty() {
+        None
+    } else {
+        Some((key, PathBuf::from(path)))
+    }
+}
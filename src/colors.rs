@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use lscolors::LsColors;
+use ratatui::style::{Color, Modifier, Style};
+
+// the built-in theme in LS_COLORS syntax, used when the variable is not set
+// di=directory ln=symlink or=broken symlink ex=executable so=socket pi=fifo bd/cd=device
+const DEFAULT_LS_COLORS: &str = "di=1;34:ln=1;36:or=1;31:mi=1;31:ex=1;32:so=1;35:pi=33:\
+bd=1;33:cd=1;33:su=37;41:sg=30;43:tw=30;42:ow=34;42:\
+*.tar=31:*.tgz=31:*.gz=31:*.xz=31:*.zst=31:*.bz2=31:*.zip=31:*.7z=31:*.rar=31:*.deb=31:*.rpm=31:\
+*.jpg=35:*.jpeg=35:*.png=35:*.gif=35:*.bmp=35:*.svg=35:*.webp=35:*.ico=35:\
+*.mp3=36:*.flac=36:*.wav=36:*.ogg=36:*.mp4=35:*.mkv=35:*.webm=35:*.avi=35:\
+*.md=33:*.txt=37:*.pdf=91:*.toml=93:*.json=93:*.yaml=93:*.yml=93:*.lock=90";
+
+// LS_COLORS from the environment or the built-in theme
+pub fn load_ls_colors() -> LsColors {
+    LsColors::from_env().unwrap_or_else(|| LsColors::from_string(DEFAULT_LS_COLORS))
+}
+
+// the style of an entry by its file type and extension
+pub fn style_for(entry: &Path, ls_colors: &LsColors) -> Style {
+    match ls_colors.style_for_path(entry) {
+        Some(ls_style) => {
+            let mut style = Style::default();
+            if let Some(color) = ls_style.foreground {
+                style = style.fg(convert_color(color));
+            }
+            if let Some(color) = ls_style.background {
+                style = style.bg(convert_color(color));
+            }
+            let font = &ls_style.font_style;
+            for (enabled, modifier) in [
+                (font.bold, Modifier::BOLD),
+                (font.dimmed, Modifier::DIM),
+                (font.italic, Modifier::ITALIC),
+                (font.underline, Modifier::UNDERLINED),
+                (font.slow_blink, Modifier::SLOW_BLINK),
+                (font.rapid_blink, Modifier::RAPID_BLINK),
+                (font.reverse, Modifier::REVERSED),
+                (font.hidden, Modifier::HIDDEN),
+                (font.strikethrough, Modifier::CROSSED_OUT),
+            ] {
+                if enabled {
+                    style = style.add_modifier(modifier);
+                }
+            }
+            style
+        }
+        None => Style::default(),
+    }
+}
+
+fn convert_color(color: lscolors::Color) -> Color {
+    match color {
+        lscolors::Color::Black => Color::Black,
+        lscolors::Color::Red => Color::Red,
+        lscolors::Color::Green => Color::Green,
+        lscolors::Color::Yellow => Color::Yellow,
+        lscolors::Color::Blue => Color::Blue,
+        lscolors::Color::Magenta => Color::Magenta,
+        lscolors::Color::Cyan => Color::Cyan,
+        lscolors::Color::White => Color::Gray,
+        lscolors::Color::BrightBlack => Color::DarkGray,
+        lscolors::Color::BrightRed => Color::LightRed,
+        lscolors::Color::BrightGreen => Color::LightGreen,
+        lscolors::Color::BrightYellow => Color::LightYellow,
+        lscolors::Color::BrightBlue => Color::LightBlue,
+        lscolors::Color::BrightMagenta => Color::LightMagenta,
+        lscolors::Color::BrightCyan => Color::LightCyan,
+        lscolors::Color::BrightWhite => Color::White,
+        lscolors::Color::Fixed(index) => Color::Indexed(index),
+        lscolors::Color::RGB(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
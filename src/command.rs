@@ -0,0 +1,298 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::file_list::PaneLayout;
+use crate::keymap::{ACTIONS, Action};
+use crate::path_input;
+use crate::sort::SortMode;
+
+// how many command lines are kept in $XDG_DATA_HOME/zfile/command_history
+const MAX_HISTORY: usize = 100;
+
+// the commands that take an argument, the actions are commands by their name too
+const COMMANDS: &[&str] = &[
+    "cd", "mkdir", "touch", "rename", "copy", "move", "select", "deselect", "sort", "set",
+];
+
+// the options of ":set", "hidden" turns it on, "nohidden" off and "hidden!" switches it
+const FLAGS: &[&str] = &["hidden", "details", "reverse"];
+const LAYOUTS: &[&str] = &["single", "dual", "miller"];
+const SORT_MODES: &[&str] = &["name", "size", "date"];
+
+// an option of ":set" that is on or off
+#[derive(Clone, Copy, PartialEq)]
+pub enum Flag {
+    Hidden,
+    Details,
+    Reverse,
+}
+
+// one line of the command mode like "cd ~/src" or "set hidden!"
+pub enum Command {
+    //an action by its name like "toggle_hidden", also "rename" or "copy" without an argument
+    Action(Action),
+    //a path like in the go to prompt, the home directory without one
+    Cd(String),
+    Mkdir(String),
+    Touch(String),
+    //the new name of the selected entry
+    Rename(String),
+    //the destination of the selected entry
+    Copy(String),
+    Move(String),
+    //a glob or a /regex like in the selection prompts
+    Select(String),
+    Deselect(String),
+    //the mode and if the order is reversed
+    Sort(SortMode, bool),
+    //None switches the flag
+    Set(Flag, Option<bool>),
+    Layout(PaneLayout),
+}
+
+pub fn parse(line: &str) -> Result<Command, String> {
+    let line = line.trim().trim_start_matches(':');
+    let (name, argument) = match line.split_once(' ') {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (line, ""),
+    };
+    match (name, argument) {
+        ("q", "") => Ok(Command::Action(Action::Quit)),
+        ("cd", path) => Ok(Command::Cd(path.to_string())),
+        ("mkdir" | "touch", "") => Err(format!("{} needs a name", name)),
+        ("mkdir", name) => Ok(Command::Mkdir(name.to_string())),
+        ("touch", name) => Ok(Command::Touch(name.to_string())),
+        ("rename", new_name) if !new_name.is_empty() => Ok(Command::Rename(new_name.to_string())),
+        ("copy", destination) if !destination.is_empty() => {
+            Ok(Command::Copy(destination.to_string()))
+        }
+        ("move", destination) if !destination.is_empty() => {
+            Ok(Command::Move(destination.to_string()))
+        }
+        ("select" | "deselect", "") => Err(format!("{} needs a glob or a /regex", name)),
+        ("select", pattern) => Ok(Command::Select(pattern.to_string())),
+        ("deselect", pattern) => Ok(Command::Deselect(pattern.to_string())),
+        ("sort", "") => Ok(Command::Action(Action::CycleSort)),
+        ("sort", "reverse") => Ok(Command::Action(Action::ReverseSort)),
+        ("sort", argument) => parse_sort(argument),
+        ("set", option) => parse_set(option),
+        (name, "") => ACTIONS
+            .iter()
+            .find(|info| info.name == name)
+            .map(|info| Command::Action(info.action))
+            .ok_or(format!("unknown command '{}'", name)),
+        (name, _) => Err(format!("'{}' takes no argument", name)),
+    }
+}
+
+// "size" or "date reverse"
+fn parse_sort(argument: &str) -> Result<Command, String> {
+    let mut words = argument.split_whitespace();
+    let mode = match words.next() {
+        Some("name") => SortMode::Name,
+        Some("size") => SortMode::Size,
+        Some("date") => SortMode::Date,
+        _ => {
+            return Err(format!(
+                "invalid sort '{}', use name, size or date",
+                argument
+            ));
+        }
+    };
+    match words.next() {
+        None => Ok(Command::Sort(mode, false)),
+        Some("reverse") => Ok(Command::Sort(mode, true)),
+        Some(word) => Err(format!("invalid sort order '{}', use reverse", word)),
+    }
+}
+
+// "hidden", "nohidden", "hidden!" or "layout=dual"
+fn parse_set(option: &str) -> Result<Command, String> {
+    if let Some(layout) = option.strip_prefix("layout=") {
+        return match layout {
+            "single" => Ok(Command::Layout(PaneLayout::Single)),
+            "dual" => Ok(Command::Layout(PaneLayout::Dual)),
+            "miller" => Ok(Command::Layout(PaneLayout::Miller)),
+            _ => Err(format!(
+                "invalid layout '{}', use single, dual or miller",
+                layout
+            )),
+        };
+    }
+    let (name, value) = if let Some(name) = option.strip_suffix('!') {
+        (name, None)
+    } else if let Some(name) = option.strip_prefix("no") {
+        (name, Some(false))
+    } else {
+        (option, Some(true))
+    };
+    let flag = match name {
+        "hidden" => Flag::Hidden,
+        "details" => Flag::Details,
+        "reverse" => Flag::Reverse,
+        _ => return Err(format!("unknown option '{}'", option)),
+    };
+    Ok(Command::Set(flag, value))
+}
+
+// what the typed line can be completed to, the whole line for every completion
+pub fn completions(input: &str, base: &Path) -> Vec<String> {
+    let Some((name, argument)) = input.split_once(' ') else {
+        //the name of a command or an action
+        let mut names: Vec<&str> = COMMANDS
+            .iter()
+            .copied()
+            .chain(ACTIONS.iter().map(|info| info.name))
+            .filter(|found| found.starts_with(input))
+            .collect();
+        names.sort();
+        names.dedup();
+        return names.into_iter().map(str::to_string).collect();
+    };
+    let words: Vec<String> = match name {
+        "cd" => path_input::completions(argument, base, true),
+        "copy" | "move" => path_input::completions(argument, base, false),
+        "sort" => SORT_MODES
+            .iter()
+            .flat_map(|mode| [mode.to_string(), format!("{} reverse", mode)])
+            .chain(["reverse".to_string()])
+            .collect(),
+        "set" => FLAGS
+            .iter()
+            .flat_map(|flag| {
+                [
+                    flag.to_string(),
+                    format!("no{}", flag),
+                    format!("{}!", flag),
+                ]
+            })
+            .chain(LAYOUTS.iter().map(|layout| format!("layout={}", layout)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    words
+        .into_iter()
+        .filter(|word| word.starts_with(argument))
+        .map(|word| format!("{} {}", name, word))
+        .collect()
+}
+
+// the command lines that ran before, the oldest first
+pub struct CommandHistory {
+    commands: Vec<String>,
+    //None if there is no data directory, then nothing gets saved
+    file: Option<PathBuf>,
+}
+
+impl CommandHistory {
+    pub fn load() -> Self {
+        let file = config::data_path("command_history");
+        let commands = file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|content| content.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        CommandHistory { commands, file }
+    }
+
+    //a line that ran again moves to the end
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        self.commands.retain(|command| command != line);
+        self.commands.push(line.to_string());
+        if self.commands.len() > MAX_HISTORY {
+            self.commands.remove(0);
+        }
+        self.save().ok();
+    }
+
+    //the line some steps back, 0 is the last one
+    pub fn get(&self, back: usize) -> Option<&String> {
+        self.commands.iter().rev().nth(back)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, self.commands.join("\n") + "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_turns_flags_on_off_and_switches_them() {
+        assert!(matches!(
+            parse("set hidden"),
+            Ok(Command::Set(Flag::Hidden, Some(true)))
+        ));
+        assert!(matches!(
+            parse("set nohidden"),
+            Ok(Command::Set(Flag::Hidden, Some(false)))
+        ));
+        assert!(matches!(
+            parse("set hidden!"),
+            Ok(Command::Set(Flag::Hidden, None))
+        ));
+        assert!(matches!(
+            parse(":set layout=dual"),
+            Ok(Command::Layout(PaneLayout::Dual))
+        ));
+        assert_eq!(
+            parse("set nothing").err(),
+            Some("unknown option 'nothing'".to_string())
+        );
+    }
+
+    #[test]
+    fn sort_takes_a_mode_and_an_order() {
+        assert!(matches!(
+            parse("sort date reverse"),
+            Ok(Command::Sort(SortMode::Date, true))
+        ));
+        assert!(matches!(
+            parse("sort size"),
+            Ok(Command::Sort(SortMode::Size, false))
+        ));
+        assert!(matches!(
+            parse("sort reverse"),
+            Ok(Command::Action(Action::ReverseSort))
+        ));
+        assert!(parse("sort date up").is_err());
+    }
+
+    #[test]
+    fn actions_are_commands_without_an_argument() {
+        assert!(matches!(
+            parse("toggle_hidden"),
+            Ok(Command::Action(Action::ToggleHidden))
+        ));
+        assert!(matches!(parse("q"), Ok(Command::Action(Action::Quit))));
+        assert_eq!(
+            parse("toggle_hidden now").err(),
+            Some("'toggle_hidden' takes no argument".to_string())
+        );
+        assert_eq!(
+            parse("frobnicate").err(),
+            Some("unknown command 'frobnicate'".to_string())
+        );
+    }
+
+    #[test]
+    fn arguments_are_trimmed() {
+        assert!(matches!(parse("cd   ~/src  "), Ok(Command::Cd(path)) if path == "~/src"));
+        assert!(matches!(parse("cd"), Ok(Command::Cd(path)) if path.is_empty()));
+        assert!(parse("mkdir").is_err());
+    }
+}
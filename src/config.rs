@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use serde::Deserialize;
+
+use crate::details::{Column, TimeFormat};
+use crate::file_list::{LineNumbers, PaneLayout};
+use crate::icons::IconOverrides;
+use crate::keymap::KeyList;
+use crate::sort::SortMode;
+
+// the user config, read from $XDG_CONFIG_HOME/zfile/config.toml
+// every field has a default so the file can be partial or missing
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Config {
+    //show dotfiles and ignored entries on startup
+    pub show_hidden: bool,
+    //globs matched against the file name, e.g. "*.pyc" or "node_modules"
+    pub ignore_globs: Vec<String>,
+    //hide entries listed in .gitignore/.ignore files of the current tree
+    pub respect_ignore_files: bool,
+    //start in the detailed list view, toggle with 'i'
+    pub detailed_view: bool,
+    //the columns of the detailed list view in order
+    pub detail_columns: Vec<Column>,
+    //"relative" or "absolute" modification times
+    pub time_format: TimeFormat,
+    //show Nerd Font icons in front of the names
+    pub show_icons: bool,
+    //replace or add icons, see [icons] in the README
+    pub icons: IconOverrides,
+    //show git status markers and the branch inside repositories
+    pub git_status: bool,
+    //"name", "size" or "date" for new tabs, cycle with 'o'
+    pub sort: SortMode,
+    //reverse the sort order for new tabs, toggle with 'O'
+    pub sort_reverse: bool,
+    //list directories before files
+    pub dirs_first: bool,
+    //"single" (list and preview), "dual" (two file lists) or "miller" (parent, list and preview)
+    pub layout: PaneLayout,
+    //the relative widths of the parent, list and preview columns of the miller layout
+    pub miller_ratios: [u16; 3],
+    //how deep the recursive finder ('F') descends from the current directory
+    pub finder_depth: usize,
+    //keep the search filter after Enter so it applies to the next directories too
+    pub keep_filter: bool,
+    //milliseconds until the keys that can follow a pressed prefix like 'g' are shown
+    pub key_hint_delay: u64,
+    //"none", "absolute" or "relative" numbers in front of the entries
+    pub line_numbers: LineNumbers,
+    //click, scroll and the right click menu, turn it off to select text in the terminal
+    pub mouse: bool,
+    //action names with the keys that replace their default keys, see '?' for the names
+    pub keys: HashMap<String, KeyList>,
+    //key sequences that run a command line like "cd ~/src"
+    pub commands: HashMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            show_hidden: false,
+            ignore_globs: Vec::new(),
+            respect_ignore_files: false,
+            detailed_view: false,
+            detail_columns: Column::all(),
+            time_format: TimeFormat::default(),
+            show_icons: false,
+            icons: IconOverrides::default(),
+            git_status: true,
+            sort: SortMode::default(),
+            sort_reverse: false,
+            dirs_first: true,
+            layout: PaneLayout::default(),
+            miller_ratios: [1, 2, 2],
+            finder_depth: 8,
+            keep_filter: false,
+            key_hint_delay: 400,
+            line_numbers: LineNumbers::default(),
+            mouse: true,
+            keys: HashMap::new(),
+            commands: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    //build the glob set for the ignore globs, invalid globs are skipped
+    pub fn ignore_globset(&self) -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.ignore_globs {
+            if let Ok(glob) = Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    }
+
+    //a walker for the whole tree below the directory that skips what the list hides
+    pub fn tree_walker(
+        &self,
+        root: &Path,
+        show_hidden: bool,
+        ignore_globs: &Arc<GlobSet>,
+    ) -> WalkBuilder {
+        let globs = Arc::clone(ignore_globs);
+        let respect = self.respect_ignore_files && !show_hidden;
+        let mut walker = WalkBuilder::new(root);
+        walker
+            .hidden(!show_hidden)
+            .parents(respect)
+            .ignore(respect)
+            .git_ignore(respect)
+            .git_global(respect)
+            .git_exclude(respect)
+            .filter_entry(move |entry| {
+                show_hidden || entry.depth() == 0 || !globs.is_match(entry.file_name())
+            });
+        walker
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("zfile").join("config.toml"))
+}
+
+// a file in $XDG_DATA_HOME/zfile for the things zfile stores itself
+pub fn data_path(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("zfile").join(name))
+}
+
+// load the config, falls back to the defaults if the file is missing or broken
+pub fn load_config() -> Config {
+    config_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,208 @@
+use std::fs::{self, Metadata};
+use std::os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt};
+use std::path::Path;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::Constraint,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Cell, Row},
+};
+use serde::Deserialize;
+use uzers::{Groups, Users, UsersCache};
+
+// the columns of the detailed list view, the name column is always shown
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Size,
+    Permissions,
+    Owner,
+    Modified,
+}
+
+impl Column {
+    pub fn all() -> Vec<Column> {
+        vec![
+            Column::Permissions,
+            Column::Owner,
+            Column::Size,
+            Column::Modified,
+        ]
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Column::Size => "Size",
+            Column::Permissions => "Mode",
+            Column::Owner => "Owner",
+            Column::Modified => "Modified",
+        }
+    }
+
+    fn width(&self) -> Constraint {
+        match self {
+            Column::Size => Constraint::Length(7),
+            Column::Permissions => Constraint::Length(10),
+            Column::Owner => Constraint::Length(17),
+            Column::Modified => Constraint::Length(16),
+        }
+    }
+}
+
+// how the modification time is shown
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    //e.g. "5m ago"
+    #[default]
+    Relative,
+    //e.g. "2024-05-01 13:37"
+    Absolute,
+}
+
+pub fn header_row(columns: &[Column]) -> Row<'static> {
+    let mut cells = vec![Cell::from("Name")];
+    cells.extend(columns.iter().map(|column| Cell::from(column.title())));
+    Row::new(cells).style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+pub fn column_widths(columns: &[Column]) -> Vec<Constraint> {
+    let mut widths = vec![Constraint::Min(10)];
+    widths.extend(columns.iter().map(Column::width));
+    widths
+}
+
+// the display name with a trailing '/' for directories and the target for symlinks
+pub fn display_name(entry: &Path, base: &Path) -> String {
+    //entries of recursive queries are shown with the path below the listed directory
+    let name = entry.strip_prefix(base).unwrap_or(entry).to_string_lossy();
+    if let Ok(target) = fs::read_link(entry) {
+        format!("{} -> {}", name, target.to_string_lossy())
+    } else if entry.is_dir() {
+        format!("{}/", name)
+    } else {
+        name.to_string()
+    }
+}
+
+// one row of the detailed list view, the name comes already decorated
+pub fn detail_row(
+    entry: &Path,
+    name: Line<'static>,
+    columns: &[Column],
+    time_format: TimeFormat,
+    users: &UsersCache,
+) -> Row<'static> {
+    //symlink_metadata so a link shows its own mode and not the one of its target
+    let metadata = fs::symlink_metadata(entry).ok();
+    let mut cells = vec![Cell::from(name)];
+    for column in columns {
+        let text = match &metadata {
+            Some(metadata) => match column {
+                Column::Size => {
+                    if metadata.is_dir() {
+                        "-".to_string()
+                    } else {
+                        human_size(metadata.len())
+                    }
+                }
+                Column::Permissions => mode_string(metadata),
+                Column::Owner => owner_string(metadata, users),
+                Column::Modified => metadata
+                    .modified()
+                    .map(|time| format_time(time, time_format))
+                    .unwrap_or_default(),
+            },
+            None => "?".to_string(),
+        };
+        cells.push(Cell::from(text));
+    }
+    Row::new(cells)
+}
+
+// size with a binary unit, e.g. "4.2K"
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else if size < 10.0 {
+        format!("{:.1}{}", size, UNITS[unit])
+    } else {
+        format!("{:.0}{}", size, UNITS[unit])
+    }
+}
+
+// the mode like ls prints it, e.g. "drwxr-xr-x"
+pub fn mode_string(metadata: &Metadata) -> String {
+    let file_type = metadata.file_type();
+    let kind = if file_type.is_dir() {
+        'd'
+    } else if file_type.is_symlink() {
+        'l'
+    } else if file_type.is_block_device() {
+        'b'
+    } else if file_type.is_char_device() {
+        'c'
+    } else if file_type.is_fifo() {
+        'p'
+    } else if file_type.is_socket() {
+        's'
+    } else {
+        '-'
+    };
+    let mode = metadata.permissions().mode();
+    let mut text = String::with_capacity(10);
+    text.push(kind);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    text
+}
+
+// "user:group", falls back to the numeric ids
+fn owner_string(metadata: &Metadata, users: &UsersCache) -> String {
+    let user = users
+        .get_user_by_uid(metadata.uid())
+        .map(|user| user.name().to_string_lossy().to_string())
+        .unwrap_or_else(|| metadata.uid().to_string());
+    let group = users
+        .get_group_by_gid(metadata.gid())
+        .map(|group| group.name().to_string_lossy().to_string())
+        .unwrap_or_else(|| metadata.gid().to_string());
+    format!("{}:{}", user, group)
+}
+
+pub fn format_time(time: SystemTime, time_format: TimeFormat) -> String {
+    match time_format {
+        TimeFormat::Absolute => {
+            let local: DateTime<Local> = time.into();
+            local.format("%Y-%m-%d %H:%M").to_string()
+        }
+        TimeFormat::Relative => {
+            let seconds = SystemTime::now()
+                .duration_since(time)
+                .map(|age| age.as_secs())
+                .unwrap_or(0);
+            match seconds {
+                0..60 => "just now".to_string(),
+                60..3600 => format!("{}m ago", seconds / 60),
+                3600..86400 => format!("{}h ago", seconds / 3600),
+                86400..2592000 => format!("{}d ago", seconds / 86400),
+                2592000..31536000 => format!("{}mo ago", seconds / 2592000),
+                _ => format!("{}y ago", seconds / 31536000),
+            }
+        }
+    }
+}
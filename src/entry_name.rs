@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use lscolors::LsColors;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::colors;
+use crate::git_status::GitInfo;
+use crate::highlight::{self, Highlight};
+use crate::icons::{self, IconOverrides};
+
+// what decorates the names in the file list and in the detailed view
+pub struct NameContext<'a> {
+    pub ls_colors: &'a LsColors,
+    //None when icons are disabled
+    pub icons: Option<&'a IconOverrides>,
+    //None outside of a git repository
+    pub git: Option<&'a GitInfo>,
+    //None when the list is not filtered by a search
+    pub highlight: Option<Highlight<'a>>,
+    //None when nothing is selected
+    pub selection: Option<&'a HashSet<PathBuf>>,
+}
+
+// how selected entries stand out
+const SELECTED_STYLE: Style = Style::new()
+    .fg(Color::LightYellow)
+    .add_modifier(Modifier::BOLD);
+
+// the name of an entry with the git marker and the icon in front, styled by its type
+pub fn name_line(entry: &Path, name: String, context: &NameContext) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut selected = false;
+    if let Some(selection) = context.selection {
+        selected = selection.contains(entry);
+        spans.push(if selected {
+            Span::styled("+ ", SELECTED_STYLE)
+        } else {
+            Span::raw("  ")
+        });
+    }
+    if let Some(git) = context.git {
+        match git.status_for(entry) {
+            Some(status) => spans.push(Span::styled(status.marker(), status.style())),
+            None => spans.push(Span::raw(" ")),
+        }
+        spans.push(Span::raw(" "));
+    }
+    let mut style = colors::style_for(entry, context.ls_colors);
+    if selected {
+        style = style.patch(SELECTED_STYLE);
+    }
+    if let Some(overrides) = context.icons {
+        spans.push(Span::styled(
+            format!("{} ", icons::icon_for(entry, overrides)),
+            style,
+        ));
+    }
+    //the search matched the file name, which comes after the directories of a recursive query
+    let file_name = entry.file_name().unwrap_or_default().to_string_lossy();
+    let offset = if name.starts_with(&*file_name) {
+        0
+    } else {
+        name.find(&format!("/{}", file_name))
+            .map(|index| name[..=index].chars().count())
+            .unwrap_or(0)
+    };
+    let indices: Vec<usize> = context
+        .highlight
+        .as_ref()
+        .map(|highlight| highlight.indices(&file_name))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|index| index + offset)
+        .collect();
+    spans.extend(highlight::spans(&name, &indices, style));
+    Line::from(spans)
+}
@@ -0,0 +1,280 @@
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Table, TableState},
+};
+use serde::Deserialize;
+use uzers::UsersCache;
+
+use crate::config::Config;
+use crate::details;
+use crate::entry_name::{self, NameContext};
+use crate::tab::Tab;
+
+// how the screen is split up
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PaneLayout {
+    //file list and preview
+    #[default]
+    Single,
+    //two independent file lists like in midnight commander
+    Dual,
+    //parent directory, file list and preview in three columns
+    Miller,
+}
+
+impl PaneLayout {
+    //the next layout when cycling with 'v'
+    pub fn next(self) -> PaneLayout {
+        match self {
+            PaneLayout::Single => PaneLayout::Dual,
+            PaneLayout::Dual => PaneLayout::Miller,
+            PaneLayout::Miller => PaneLayout::Single,
+        }
+    }
+}
+
+// the numbers in front of the entries
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LineNumbers {
+    #[default]
+    None,
+    //the position in the list
+    Absolute,
+    //the distance to the selected entry like vims relativenumber, for counts like "5j"
+    Relative,
+}
+
+impl LineNumbers {
+    //the number of an entry, the selected one shows its position
+    fn gutter(self, index: usize, selected: usize, len: usize) -> Option<Span<'static>> {
+        let number = match self {
+            LineNumbers::None => return None,
+            LineNumbers::Absolute => index + 1,
+            LineNumbers::Relative if index == selected => index + 1,
+            LineNumbers::Relative => index.abs_diff(selected),
+        };
+        let style = if index == selected {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let width = len.to_string().len();
+        Some(Span::styled(format!("{:>width$} ", number), style))
+    }
+}
+
+// what every file list is rendered with
+pub struct FileListView<'a> {
+    pub config: &'a Config,
+    pub users: &'a UsersCache,
+    //if the detailed table is shown instead of the plain list
+    pub detailed_view: bool,
+}
+
+// one file list on the screen
+pub struct Pane<'a> {
+    pub tab: &'a Tab,
+    pub entries: &'a [PathBuf],
+    pub hidden_count: usize,
+    pub name_context: NameContext<'a>,
+    //the pane that receives the keys, the other one is dimmed
+    pub focused: bool,
+}
+
+// returns the first entry on the screen and how many entries fit
+pub fn render_file_list(
+    f: &mut Frame,
+    area: Rect,
+    pane: &Pane,
+    view: &FileListView,
+) -> (usize, usize) {
+    let tab = pane.tab;
+    let entries = pane.entries;
+
+    //show the sorting and how many entries are hidden in the title
+    let mut list_title = format!("Files by {}", tab.sort_mode.label());
+    if tab.sort_reverse {
+        list_title.push_str(" (reversed)");
+    }
+    if pane.hidden_count > 0 {
+        list_title.push_str(&format!(" ({} hidden, '.' to show)", pane.hidden_count));
+    }
+    let mut list_block = Block::default().title(list_title).borders(Borders::ALL);
+    //the path at the bottom, a click on a directory of it goes there
+    let mut path_spans: Vec<Span> = breadcrumbs(&tab.current_directory)
+        .into_iter()
+        .map(|(label, _)| Span::raw(label))
+        .collect();
+    if let Some(last) = path_spans.last_mut() {
+        last.style = Style::default().add_modifier(Modifier::BOLD);
+    }
+    list_block = list_block.title_bottom(Line::from(path_spans));
+    //the current branch on the right side of the title
+    if let Some(git) = pane.name_context.git {
+        list_block = list_block.title(Line::from(format!(" {} ", git.branch)).right_aligned());
+    }
+
+    let highlight_style = if pane.focused {
+        list_block = list_block.border_style(Style::default());
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::REVERSED)
+    } else {
+        list_block = list_block.border_style(Style::default().fg(Color::DarkGray));
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED)
+    };
+
+    let line_numbers = view.config.line_numbers;
+    if view.detailed_view {
+        let columns = &view.config.detail_columns;
+        //the detailed view is a table with the columns from the config
+        let rows: Vec<_> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let mut name = entry_name::name_line(
+                    entry,
+                    details::display_name(entry, &tab.current_directory),
+                    &pane.name_context,
+                );
+                if let Some(number) = line_numbers.gutter(index, tab.selected_file, entries.len()) {
+                    name.spans.insert(0, number);
+                }
+                details::detail_row(entry, name, columns, view.config.time_format, view.users)
+            })
+            .collect();
+        let ui_table = Table::new(rows, details::column_widths(columns))
+            .header(details::header_row(columns))
+            .block(list_block)
+            .row_highlight_style(highlight_style);
+
+        //start where the last frame was so the screen only scrolls when the selection leaves it
+        let mut table_state = TableState::default().with_offset(tab.list_offset);
+        if !entries.is_empty() {
+            table_state.select(Some(tab.selected_file));
+        }
+        //render the table
+        f.render_stateful_widget(ui_table, area, &mut table_state);
+        //the borders and the header take three rows
+        (table_state.offset(), area.height.saturating_sub(3) as usize)
+    } else {
+        //declare the items for the list
+        let items: Vec<ListItem> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let mut name = entry_name::name_line(
+                    entry,
+                    list_name(entry, &tab.current_directory),
+                    &pane.name_context,
+                );
+                if let Some(number) = line_numbers.gutter(index, tab.selected_file, entries.len()) {
+                    name.spans.insert(0, number);
+                }
+                ListItem::new(name)
+            })
+            .collect();
+
+        //declaring a "frame" for the list where it can be rendered
+        let ui_list = List::new(items)
+            .block(list_block)
+            .highlight_style(highlight_style);
+
+        let mut list_state = ListState::default().with_offset(tab.list_offset);
+        if !entries.is_empty() {
+            list_state.select(Some(tab.selected_file));
+        }
+        //render the list
+        f.render_stateful_widget(ui_list, area, &mut list_state);
+        (list_state.offset(), area.height.saturating_sub(2) as usize)
+    }
+}
+
+// the directories of the path from the root with their label like "home/" for the breadcrumbs
+pub fn breadcrumbs(directory: &Path) -> Vec<(String, PathBuf)> {
+    let mut breadcrumbs: Vec<(String, PathBuf)> = directory
+        .ancestors()
+        .map(|ancestor| {
+            let label = match ancestor.file_name() {
+                Some(name) => format!("{}/", name.to_string_lossy()),
+                None => ancestor.to_string_lossy().to_string(),
+            };
+            (label, ancestor.to_path_buf())
+        })
+        .collect();
+    breadcrumbs.reverse();
+    //the current directory without the trailing '/'
+    if breadcrumbs.len() > 1
+        && let Some((label, _)) = breadcrumbs.last_mut()
+    {
+        label.pop();
+    }
+    breadcrumbs
+}
+
+// the directory of the breadcrumbs at the bottom border of a file list under a column
+pub fn breadcrumb_at(directory: &Path, area: Rect, column: u16) -> Option<PathBuf> {
+    //the title starts after the corner of the border
+    let mut x = area.x + 1;
+    for (label, path) in breadcrumbs(directory) {
+        let width = label.chars().count() as u16;
+        if (x..x + width).contains(&column) {
+            return Some(path);
+        }
+        x += width;
+    }
+    None
+}
+
+// the name with a trailing '/' for directories, entries of recursive queries
+// are shown with the path below the listed directory
+fn list_name(entry: &Path, base: &Path) -> String {
+    let name = entry.strip_prefix(base).unwrap_or(entry).to_string_lossy();
+    if entry.is_dir() {
+        format!("{}/", name)
+    } else {
+        name.to_string()
+    }
+}
+
+// the parent column of the miller layout with the current directory selected
+pub fn render_parent_list(
+    f: &mut Frame,
+    area: Rect,
+    current_directory: &Path,
+    parent_entries: &[PathBuf],
+    name_context: &NameContext,
+) {
+    let parent = current_directory.parent().unwrap_or(current_directory);
+    let title = parent.to_string_lossy().to_string();
+    let items: Vec<ListItem> = parent_entries
+        .iter()
+        .map(|entry| {
+            ListItem::new(entry_name::name_line(
+                entry,
+                list_name(entry, parent),
+                name_context,
+            ))
+        })
+        .collect();
+    let ui_list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut list_state = ListState::default();
+    list_state.select(
+        parent_entries
+            .iter()
+            .position(|entry| entry == current_directory),
+    );
+    f.render_stateful_widget(ui_list, area, &mut list_state);
+}
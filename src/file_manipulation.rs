@@ -1,28 +1,32 @@
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
 
-pub fn create_file(parent: &PathBuf, name: &str) -> io::Result<()> {
+pub fn create_file(parent: &Path, name: &str) -> io::Result<()> {
     let new_path = parent.join(name);
     fs::File::create(new_path)?;
     Ok(())
 }
-pub fn rename_file(old_path: &PathBuf, new_name: &str) -> io::Result<()> {
-    let parent = old_path.parent().ok_or_else(|| {
-        io::Error::new(io::ErrorKind::Other, "Could not determine parent directory")
-    })?;
+pub fn create_directory(parent: &Path, name: &str) -> io::Result<()> {
+    fs::create_dir_all(parent.join(name))
+}
+pub fn rename_file(old_path: &Path, new_name: &str) -> io::Result<()> {
+    let parent = old_path
+        .parent()
+        .ok_or_else(|| io::Error::other("Could not determine parent directory"))?;
     let new_path = parent.join(new_name);
     fs::rename(old_path, new_path)?;
     Ok(())
 }
 
-pub fn move_file(old_path: &PathBuf, new_path_str: &str) -> io::Result<()> {
+pub fn move_file(old_path: &Path, new_path_str: &str) -> io::Result<()> {
     // in work
     let new_path = PathBuf::from(new_path_str);
     if new_path.is_dir() {
         let file_name = old_path
             .file_name()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Invalid file name"))?;
+            .ok_or_else(|| io::Error::other("Invalid file name"))?;
         let destination = new_path.join(file_name);
         fs::rename(old_path, destination)?;
     } else {
@@ -31,7 +35,41 @@ pub fn move_file(old_path: &PathBuf, new_path_str: &str) -> io::Result<()> {
     Ok(())
 }
 
-pub fn delete_file(path: &PathBuf) -> io::Result<()> {
+pub fn copy_file(old_path: &Path, new_path_str: &str) -> io::Result<()> {
+    let new_path = PathBuf::from(new_path_str);
+    let destination = if new_path.is_dir() {
+        let file_name = old_path
+            .file_name()
+            .ok_or_else(|| io::Error::other("Invalid file name"))?;
+        new_path.join(file_name)
+    } else {
+        new_path
+    };
+    copy_recursive(old_path, &destination)
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    //a link is copied as a link, following it could copy a whole tree or loop forever
+    let file_type = fs::symlink_metadata(from)?.file_type();
+    if file_type.is_symlink() {
+        symlink(fs::read_link(from)?, to)?;
+    } else if file_type.is_dir() {
+        //copying a directory into itself would never end
+        if to.starts_with(from) {
+            return Err(io::Error::other("Cannot copy a directory into itself"));
+        }
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+pub fn delete_file(path: &Path) -> io::Result<()> {
     if path.is_dir() {
         fs::remove_dir_all(path)?;
     } else {
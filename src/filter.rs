@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use globset::{GlobBuilder, GlobMatcher};
+use regex::{Regex, RegexBuilder};
+
+// how the search query filters the entries of a directory
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum FilterMode {
+    //case insensitive part of the name, 's'
+    #[default]
+    Substring,
+    //fuzzy matched and sorted by score, 'f'
+    Fuzzy,
+    //a regex found in the name, '/'
+    Regex,
+    //a shell glob for the whole name like "*.rs", '*'
+    Glob,
+}
+
+impl FilterMode {
+    //the title of the search bar while typing
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterMode::Substring => "Search",
+            FilterMode::Fuzzy => "Search (Fuzzy)",
+            FilterMode::Regex => "Search (Regex)",
+            FilterMode::Glob => "Search (Glob)",
+        }
+    }
+}
+
+// a regex or glob query compiled once instead of every time the entries are listed
+enum Pattern {
+    Regex(Regex),
+    Glob(GlobMatcher),
+}
+
+// the compiled pattern of the last query, every tab keeps its own
+#[derive(Default)]
+pub struct FilterCache {
+    //the query and mode it was compiled from, the pattern or why it could not be compiled
+    compiled: Option<(String, FilterMode, Result<Pattern, String>)>,
+}
+
+impl FilterCache {
+    //compile the query unless it is the same as the last time
+    fn get(&mut self, query: &str, mode: FilterMode) -> Result<&Pattern, String> {
+        if let Some((last, last_mode, _)) = &self.compiled
+            && (last != query || *last_mode != mode)
+        {
+            self.compiled = None;
+        }
+        let (_, _, pattern) = self
+            .compiled
+            .get_or_insert_with(|| (query.to_string(), mode, compile(query, mode)));
+        pattern.as_ref().map_err(String::clone)
+    }
+
+    //the regex compiled for the query, for highlighting what it matched
+    pub fn regex(&self, query: &str) -> Option<&Regex> {
+        match &self.compiled {
+            Some((last, FilterMode::Regex, Ok(Pattern::Regex(regex)))) if last == query => {
+                Some(regex)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn compile(query: &str, mode: FilterMode) -> Result<Pattern, String> {
+    if mode == FilterMode::Glob {
+        let glob = GlobBuilder::new(query)
+            .build()
+            .map_err(|error| error.kind().to_string())?;
+        Ok(Pattern::Glob(glob.compile_matcher()))
+    } else {
+        //only the last line of a regex error says what is wrong
+        smart_case_regex(query)
+            .map(Pattern::Regex)
+            .map_err(|error| error.to_string().lines().last().unwrap_or("").to_string())
+    }
+}
+
+// the entries whose name matches a glob, or a regex when the pattern starts with '/'
+pub fn pattern_entries(
+    entries: &[PathBuf],
+    pattern: &str,
+    matcher: &SkimMatcherV2,
+) -> Result<Vec<PathBuf>, String> {
+    match pattern.strip_prefix('/') {
+        Some(regex) => filter_entries(
+            entries.to_vec(),
+            regex,
+            FilterMode::Regex,
+            matcher,
+            &mut FilterCache::default(),
+        ),
+        None => filter_entries(
+            entries.to_vec(),
+            pattern,
+            FilterMode::Glob,
+            matcher,
+            &mut FilterCache::default(),
+        ),
+    }
+}
+
+// smart case like in the content search, the pattern ignores case while it is all lowercase
+pub fn smart_case_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!pattern.chars().any(char::is_uppercase))
+        .build()
+}
+
+// the entries whose name matches the query, an invalid regex or glob is returned as error
+pub fn filter_entries(
+    entries: Vec<PathBuf>,
+    query: &str,
+    mode: FilterMode,
+    matcher: &SkimMatcherV2,
+    cache: &mut FilterCache,
+) -> Result<Vec<PathBuf>, String> {
+    if query.is_empty() {
+        return Ok(entries);
+    }
+    let name = |path: &PathBuf| {
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    };
+    let filtered = match mode {
+        FilterMode::Substring => {
+            let query = query.to_lowercase();
+            entries
+                .into_iter()
+                .filter(|path| name(path).to_lowercase().contains(&query))
+                .collect()
+        }
+        FilterMode::Fuzzy => {
+            let mut scored: Vec<(PathBuf, i64)> = entries
+                .into_iter()
+                .filter_map(|path| {
+                    matcher
+                        .fuzzy_match(&name(&path), query)
+                        .map(|score| (path, score))
+                })
+                .collect();
+            scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+            scored.into_iter().map(|(path, _)| path).collect()
+        }
+        FilterMode::Regex | FilterMode::Glob => {
+            let pattern = cache.get(query, mode)?;
+            entries
+                .into_iter()
+                .filter(|path| match pattern {
+                    Pattern::Regex(regex) => regex.is_match(&name(path)),
+                    Pattern::Glob(glob) => glob.is_match(name(path)),
+                })
+                .collect()
+        }
+    };
+    Ok(filtered)
+}
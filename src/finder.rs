@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use globset::GlobSet;
+
+use crate::config::Config;
+
+// how many matches are listed, the rest would not fit on the screen anyway
+const MAX_RESULTS: usize = 500;
+
+// the recursive finder, walks the tree from a directory on a background thread
+pub struct Finder {
+    pub root: PathBuf,
+    //the paths relative to the root, the walker sends them as it finds them
+    receiver: Receiver<PathBuf>,
+    paths: Vec<PathBuf>,
+    //false once the walker is through
+    pub searching: bool,
+    //the matches of the last query, only recomputed when something changed
+    matches: Vec<PathBuf>,
+    matched_query: String,
+    matched_count: usize,
+}
+
+impl Finder {
+    //start walking, dropping the finder stops the walker at its next entry
+    pub fn start(
+        root: PathBuf,
+        show_hidden: bool,
+        config: &Config,
+        ignore_globs: &Arc<GlobSet>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let walker = config
+            .tree_walker(&root, show_hidden, ignore_globs)
+            .max_depth(Some(config.finder_depth))
+            .build();
+        let walk_root = root.clone();
+        thread::spawn(move || {
+            for entry in walker.filter_map(Result::ok) {
+                let Ok(relative) = entry.path().strip_prefix(&walk_root) else {
+                    continue;
+                };
+                if entry.depth() > 0 && sender.send(relative.to_path_buf()).is_err() {
+                    break;
+                }
+            }
+        });
+        Finder {
+            root,
+            receiver,
+            paths: Vec::new(),
+            searching: true,
+            matches: Vec::new(),
+            matched_query: String::new(),
+            matched_count: usize::MAX,
+        }
+    }
+
+    //take the new paths of the walker and match them against the query, the best first
+    pub fn update(&mut self, query: &str, matcher: &SkimMatcherV2) -> &[PathBuf] {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(path) => self.paths.push(path),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.searching = false;
+                    break;
+                }
+            }
+        }
+        if query != self.matched_query || self.paths.len() != self.matched_count {
+            self.matches = if query.is_empty() {
+                self.paths.iter().take(MAX_RESULTS).cloned().collect()
+            } else {
+                //the whole relative path is matched so "src/main" finds src/main.rs
+                let mut scored: Vec<(i64, &PathBuf)> = self
+                    .paths
+                    .iter()
+                    .filter_map(|path| {
+                        matcher
+                            .fuzzy_match(&path.to_string_lossy(), query)
+                            .map(|score| (score, path))
+                    })
+                    .collect();
+                scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+                scored
+                    .into_iter()
+                    .take(MAX_RESULTS)
+                    .map(|(_, path)| path.clone())
+                    .collect()
+            };
+            self.matched_query = query.to_string();
+            self.matched_count = self.paths.len();
+        }
+        &self.matches
+    }
+
+    //how many paths the walker found so far
+    pub fn found(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn absolute(&self, relative: &Path) -> PathBuf {
+        self.root.join(relative)
+    }
+}
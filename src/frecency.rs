@@ -0,0 +1,221 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::config;
+
+// when the ranks add up to more than this, all of them get aged like zoxide does
+const MAX_TOTAL_RANK: f64 = 10000.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+// one visited directory
+struct Entry {
+    path: PathBuf,
+    //how often it was visited, aged over time
+    rank: f64,
+    //seconds since the epoch
+    last_access: u64,
+}
+
+impl Entry {
+    //recently visited directories count more
+    fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_access);
+        if age < HOUR {
+            self.rank * 4.0
+        } else if age < DAY {
+            self.rank * 2.0
+        } else if age < WEEK {
+            self.rank * 0.5
+        } else {
+            self.rank * 0.25
+        }
+    }
+}
+
+// every directory zfile visits with its frecency, one "<rank>\t<last access>\t<path>"
+// per line in $XDG_DATA_HOME/zfile/frecency
+pub struct Frecency {
+    entries: Vec<Entry>,
+    //None if there is no data directory, then nothing gets saved
+    file: Option<PathBuf>,
+    //the directory of the last visit so staying in it is not counted again
+    last_visit: Option<PathBuf>,
+}
+
+impl Frecency {
+    pub fn load() -> Self {
+        let file = config::data_path("frecency");
+        let entries = file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|content| content.lines().filter_map(parse_line).collect())
+            .unwrap_or_default();
+        Frecency {
+            entries,
+            file,
+            last_visit: None,
+        }
+    }
+
+    //count a visit of the directory and save the database
+    pub fn visit(&mut self, directory: &Path) {
+        if self.last_visit.as_deref() == Some(directory) {
+            return;
+        }
+        self.last_visit = Some(directory.to_path_buf());
+        self.add(directory.to_path_buf(), 1.0, now());
+        self.age();
+        self.save().ok();
+    }
+
+    fn add(&mut self, path: PathBuf, rank: f64, last_access: u64) {
+        match self.entries.iter_mut().find(|entry| entry.path == path) {
+            Some(entry) => {
+                entry.rank += rank;
+                entry.last_access = entry.last_access.max(last_access);
+            }
+            None => self.entries.push(Entry {
+                path,
+                rank,
+                last_access,
+            }),
+        }
+    }
+
+    //forget old directories once the database gets too big
+    fn age(&mut self) {
+        let total: f64 = self.entries.iter().map(|entry| entry.rank).sum();
+        if total > MAX_TOTAL_RANK {
+            for entry in &mut self.entries {
+                entry.rank *= 0.9 * MAX_TOTAL_RANK / total;
+            }
+            self.entries.retain(|entry| entry.rank >= 1.0);
+        }
+    }
+
+    //the existing directories matching every term of the query, the best first
+    pub fn query(&self, query: &str, matcher: &SkimMatcherV2) -> Vec<PathBuf> {
+        let now = now();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        let mut matches: Vec<(f64, &PathBuf)> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                let path = entry.path.to_string_lossy();
+                terms
+                    .iter()
+                    .all(|term| matcher.fuzzy_match(&path, term).is_some())
+            })
+            .filter(|entry| entry.path.is_dir())
+            .map(|entry| (entry.score(now), &entry.path))
+            .collect();
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0));
+        matches.into_iter().map(|(_, path)| path.clone()).collect()
+    }
+
+    //import the database of zoxide or autojump, returns how many directories were imported
+    pub fn import(&mut self, source: &str) -> io::Result<usize> {
+        let imported: Vec<(f64, PathBuf)> = match source {
+            //zoxide stores a binary file, its cli is the stable way to read it
+            "zoxide" => {
+                let output = Command::new("zoxide")
+                    .args(["query", "--list", "--score"])
+                    .output()?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    return Err(io::Error::other(if stderr.is_empty() {
+                        format!("zoxide failed with {}", output.status)
+                    } else {
+                        stderr
+                    }));
+                }
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter_map(|line| {
+                        let (score, path) = line.trim().split_once(' ')?;
+                        Some((score.parse().ok()?, PathBuf::from(path.trim())))
+                    })
+                    .collect()
+            }
+            //autojump keeps "<weight>\t<path>" lines
+            "autojump" => {
+                let file = dirs::data_dir()
+                    .map(|dir| dir.join("autojump").join("autojump.txt"))
+                    .ok_or_else(|| io::Error::other("No data directory"))?;
+                fs::read_to_string(file)?
+                    .lines()
+                    .filter_map(|line| {
+                        let (weight, path) = line.split_once('\t')?;
+                        Some((weight.parse().ok()?, PathBuf::from(path)))
+                    })
+                    .collect()
+            }
+            _ => {
+                return Err(io::Error::other(format!(
+                    "Unknown source '{}', use zoxide or autojump",
+                    source
+                )));
+            }
+        };
+
+        let count = imported.len();
+        let now = now();
+        for (rank, path) in imported {
+            self.add(path, rank, now);
+        }
+        //aging once after all of them keeps the import linear
+        self.age();
+        self.save()?;
+        Ok(count)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content: String = self
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    entry.rank,
+                    entry.last_access,
+                    entry.path.to_string_lossy()
+                )
+            })
+            .collect();
+        fs::write(file, content)
+    }
+}
+
+fn parse_line(line: &str) -> Option<Entry> {
+    let mut parts = line.splitn(3, '\t');
+    let rank = parts.next()?.parse().ok()?;
+    let last_access = parts.next()?.parse().ok()?;
+    let path = PathBuf::from(parts.next()?);
+    Some(Entry {
+        path,
+        rank,
+        last_access,
+    })
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0)
+}
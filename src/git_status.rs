@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use git2::{Repository, Status, StatusOptions};
+use ratatui::style::{Color, Style};
+
+// how often the status is read again while staying in the same directory
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+// the status of one entry, ordered by priority so a directory shows its most important child
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GitStatus {
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+impl GitStatus {
+    pub fn marker(&self) -> &'static str {
+        match self {
+            GitStatus::Ignored => "!",
+            GitStatus::Untracked => "?",
+            GitStatus::Staged => "S",
+            GitStatus::Modified => "M",
+            GitStatus::Conflicted => "U",
+        }
+    }
+
+    pub fn style(&self) -> Style {
+        match self {
+            GitStatus::Ignored => Style::default().fg(Color::DarkGray),
+            GitStatus::Untracked => Style::default().fg(Color::LightMagenta),
+            GitStatus::Staged => Style::default().fg(Color::Green),
+            GitStatus::Modified => Style::default().fg(Color::Yellow),
+            GitStatus::Conflicted => Style::default().fg(Color::Red),
+        }
+    }
+
+    fn from_status(status: Status) -> Option<GitStatus> {
+        if status.is_conflicted() {
+            Some(GitStatus::Conflicted)
+        } else if status.intersects(
+            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+        ) {
+            Some(GitStatus::Modified)
+        } else if status.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            Some(GitStatus::Staged)
+        } else if status.is_wt_new() {
+            Some(GitStatus::Untracked)
+        } else if status.is_ignored() {
+            Some(GitStatus::Ignored)
+        } else {
+            None
+        }
+    }
+}
+
+// the branch and the status of every changed path of a repository
+pub struct GitInfo {
+    pub branch: String,
+    workdir: PathBuf,
+    //paths relative to the workdir, directories hold the status of their children
+    statuses: HashMap<PathBuf, GitStatus>,
+}
+
+impl GitInfo {
+    pub fn status_for(&self, entry: &Path) -> Option<GitStatus> {
+        let relative = match entry.strip_prefix(&self.workdir) {
+            Ok(relative) => relative.to_path_buf(),
+            //the entry might be reached through a symlinked directory
+            Err(_) => {
+                let parent = entry.parent()?.canonicalize().ok()?;
+                parent
+                    .join(entry.file_name()?)
+                    .strip_prefix(&self.workdir)
+                    .ok()?
+                    .to_path_buf()
+            }
+        };
+        self.statuses.get(&relative).copied()
+    }
+}
+
+// read the repository that contains the directory, None outside of a repository
+pub fn load_git_info(dir: &Path) -> Option<GitInfo> {
+    let repo = Repository::discover(dir).ok()?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(true)
+        .recurse_untracked_dirs(false)
+        .recurse_ignored_dirs(false)
+        .exclude_submodules(true);
+
+    let mut statuses: HashMap<PathBuf, GitStatus> = HashMap::new();
+    for entry in repo.statuses(Some(&mut options)).ok()?.iter() {
+        let (Some(path), Some(status)) = (entry.path(), GitStatus::from_status(entry.status()))
+        else {
+            continue;
+        };
+        //untracked and ignored directories come with a trailing '/'
+        let path = PathBuf::from(path.trim_end_matches('/'));
+        merge_status(&mut statuses, path.clone(), status);
+
+        //directories aggregate the status of their children, but being ignored is not inherited
+        if status != GitStatus::Ignored {
+            for ancestor in path.ancestors().skip(1) {
+                if ancestor.as_os_str().is_empty() {
+                    break;
+                }
+                merge_status(&mut statuses, ancestor.to_path_buf(), status);
+            }
+        }
+    }
+
+    Some(GitInfo {
+        branch: branch_name(&repo),
+        workdir,
+        statuses,
+    })
+}
+
+fn merge_status(statuses: &mut HashMap<PathBuf, GitStatus>, path: PathBuf, status: GitStatus) {
+    let current = statuses.entry(path).or_insert(status);
+    if status > *current {
+        *current = status;
+    }
+}
+
+fn branch_name(repo: &Repository) -> String {
+    match repo.head() {
+        Ok(head) if repo.head_detached().unwrap_or(false) => head
+            .target()
+            .map(|oid| format!("detached at {}", &oid.to_string()[..7]))
+            .unwrap_or_else(|| "detached".to_string()),
+        Ok(head) => head.shorthand().unwrap_or("HEAD").to_string(),
+        //a fresh repository without commits has no head yet
+        Err(_) => repo
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(String::from))
+            .map(|target| target.trim_start_matches("refs/heads/").to_string())
+            .unwrap_or_else(|| "HEAD".to_string()),
+    }
+}
+
+// keeps the git info of the current directory so it's not read on every frame,
+// reading the status of a big repository takes a while so it runs on a worker thread
+pub struct GitCache {
+    //the directory the info was read for
+    dir: PathBuf,
+    loaded_at: Instant,
+    info: Option<GitInfo>,
+    //the directory being read and where its info arrives, one read at a time
+    loading: Option<(PathBuf, Receiver<Option<GitInfo>>)>,
+}
+
+impl GitCache {
+    pub fn new() -> Self {
+        GitCache {
+            dir: PathBuf::new(),
+            loaded_at: Instant::now(),
+            info: None,
+            loading: None,
+        }
+    }
+
+    //the info of the last read, it stays while moving around inside the same repository
+    pub fn get(&mut self, dir: &Path) -> Option<&GitInfo> {
+        if let Some((loading_dir, receiver)) = &self.loading {
+            match receiver.try_recv() {
+                Ok(info) => {
+                    self.dir = loading_dir.clone();
+                    self.info = info;
+                    self.loaded_at = Instant::now();
+                    self.loading = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.loading = None,
+            }
+        }
+        if self.dir != dir
+            && !self
+                .info
+                .as_ref()
+                .is_some_and(|info| dir.starts_with(&info.workdir))
+        {
+            self.info = None;
+        }
+        //a read for a directory that was left already is followed by one for the new directory
+        if self.loading.is_none()
+            && (self.dir != dir || self.loaded_at.elapsed() >= REFRESH_INTERVAL)
+        {
+            let (sender, receiver) = mpsc::channel();
+            let loading_dir = dir.to_path_buf();
+            thread::spawn(move || sender.send(load_git_info(&loading_dir)).ok());
+            self.loading = Some((dir.to_path_buf(), receiver));
+        }
+        self.info.as_ref()
+    }
+}
@@ -0,0 +1,202 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use globset::GlobSet;
+use regex::Regex;
+
+use crate::config::Config;
+use crate::filter;
+
+// stop after this many matches, more would not help finding anything
+const MAX_MATCHES: usize = 2000;
+// a file with a zero byte in its beginning is treated as binary and skipped
+const BINARY_CHECK_BYTES: usize = 8192;
+// bigger files are skipped, they are rarely worth searching and take long to read
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+// long lines are cut in the results list
+const MAX_TEXT_CHARS: usize = 200;
+
+// one matching line
+pub struct GrepMatch {
+    //relative to the directory the search started in
+    pub path: PathBuf,
+    //starting at 1 like in editors
+    pub line: usize,
+    //the line without its indentation
+    pub text: String,
+    //the char indices of the match in the text
+    pub start: usize,
+    pub end: usize,
+}
+
+// the content search, greps the files below a directory on a worker thread
+pub struct Grep {
+    pub root: PathBuf,
+    pub pattern: String,
+    //the pattern is a regex, otherwise it is matched literally
+    pub regex: bool,
+    //why the pattern could not be compiled
+    pub error: Option<String>,
+    //false once the worker is through
+    pub searching: bool,
+    receiver: Option<Receiver<GrepMatch>>,
+    matches: Vec<GrepMatch>,
+    //set when the search is replaced or closed so the worker stops
+    cancel: Arc<AtomicBool>,
+}
+
+impl Grep {
+    pub fn start(
+        root: PathBuf,
+        pattern: &str,
+        regex: bool,
+        show_hidden: bool,
+        config: &Config,
+        ignore_globs: &Arc<GlobSet>,
+    ) -> Self {
+        let mut grep = Grep {
+            root: root.clone(),
+            pattern: pattern.to_string(),
+            regex,
+            error: None,
+            searching: false,
+            receiver: None,
+            matches: Vec::new(),
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+        if pattern.is_empty() {
+            return grep;
+        }
+        let compiled = match compile(pattern, regex) {
+            Ok(compiled) => compiled,
+            Err(error) => {
+                //the last line of a regex error says what is wrong
+                grep.error = error.to_string().lines().last().map(str::to_string);
+                return grep;
+            }
+        };
+
+        let walker = config.tree_walker(&root, show_hidden, ignore_globs).build();
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::clone(&grep.cancel);
+        thread::spawn(move || {
+            let mut count = 0;
+            for entry in walker.filter_map(Result::ok) {
+                if cancel.load(Ordering::Relaxed) || count >= MAX_MATCHES {
+                    break;
+                }
+                if entry.file_type().is_some_and(|kind| kind.is_file()) {
+                    count +=
+                        grep_file(entry.path(), &root, &compiled, MAX_MATCHES - count, &sender);
+                }
+            }
+        });
+        grep.searching = true;
+        grep.receiver = Some(receiver);
+        grep
+    }
+
+    //take the new matches of the worker
+    pub fn update(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        loop {
+            match receiver.try_recv() {
+                Ok(found) => self.matches.push(found),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.searching = false;
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn matches(&self) -> &[GrepMatch] {
+        &self.matches
+    }
+
+    pub fn absolute(&self, relative: &Path) -> PathBuf {
+        self.root.join(relative)
+    }
+}
+
+impl Drop for Grep {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+// a literal pattern is escaped, both are smart case
+fn compile(pattern: &str, regex: bool) -> Result<Regex, regex::Error> {
+    if regex {
+        filter::smart_case_regex(pattern)
+    } else {
+        filter::smart_case_regex(&regex::escape(pattern))
+    }
+}
+
+// send the matching lines of the file but at most limit of them, returns how many were sent
+fn grep_file(
+    path: &Path,
+    root: &Path,
+    regex: &Regex,
+    limit: usize,
+    sender: &Sender<GrepMatch>,
+) -> usize {
+    let Ok(file) = File::open(path) else {
+        return 0;
+    };
+    if file
+        .metadata()
+        .map_or(true, |metadata| metadata.len() > MAX_FILE_SIZE)
+    {
+        return 0;
+    }
+    let mut reader = BufReader::with_capacity(BINARY_CHECK_BYTES, file);
+    match reader.fill_buf() {
+        Ok(start) if !start.contains(&0) => {}
+        _ => return 0,
+    }
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let mut bytes = Vec::new();
+    let mut count = 0;
+    let mut index = 0;
+    while count < limit {
+        bytes.clear();
+        match reader.read_until(b'\n', &mut bytes) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => index += 1,
+        }
+        let content = String::from_utf8_lossy(&bytes);
+        let line = content.trim_end_matches(['\n', '\r']);
+        let Some(found) = regex.find(line) else {
+            continue;
+        };
+        let indent = line.len() - line.trim_start().len();
+        let text: String = line.trim_start().chars().take(MAX_TEXT_CHARS).collect();
+        let start = line[indent..found.start().max(indent)].chars().count();
+        let end = start
+            + line[found.start().max(indent)..found.end().max(indent)]
+                .chars()
+                .count();
+        let grep_match = GrepMatch {
+            path: relative.to_path_buf(),
+            line: index,
+            text,
+            start,
+            end,
+        };
+        if sender.send(grep_match).is_err() {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
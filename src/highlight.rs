@@ -0,0 +1,98 @@
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use regex::Regex;
+
+use crate::filter::FilterMode;
+
+// how the matched characters stand out from the rest of the name
+const MATCH_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+// the search query the names are matched against to show why they matched
+pub struct Highlight<'a> {
+    query: String,
+    mode: FilterMode,
+    matcher: &'a SkimMatcherV2,
+    //the regex the filter compiled, None if it is invalid
+    regex: Option<&'a Regex>,
+}
+
+impl<'a> Highlight<'a> {
+    pub fn new(
+        query: &str,
+        mode: FilterMode,
+        matcher: &'a SkimMatcherV2,
+        regex: Option<&'a Regex>,
+    ) -> Self {
+        Highlight {
+            query: query.to_string(),
+            mode,
+            matcher,
+            regex,
+        }
+    }
+
+    //the char indices of the text that match the query
+    pub fn indices(&self, text: &str) -> Vec<usize> {
+        if self.query.is_empty() {
+            return Vec::new();
+        }
+        match self.mode {
+            FilterMode::Fuzzy => self
+                .matcher
+                .fuzzy_indices(text, &self.query)
+                .map(|(_, indices)| indices)
+                .unwrap_or_default(),
+            FilterMode::Substring => {
+                //compare char by char so the indices stay right for non ascii names
+                let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+                let query: Vec<char> = self.query.chars().flat_map(char::to_lowercase).collect();
+                text.windows(query.len())
+                    .position(|window| window == query.as_slice())
+                    .map(|start| (start..start + query.len()).collect())
+                    .unwrap_or_default()
+            }
+            FilterMode::Regex => self
+                .regex
+                .iter()
+                .flat_map(|regex| regex.find_iter(text))
+                .flat_map(|found| {
+                    let start = text[..found.start()].chars().count();
+                    start..start + found.as_str().chars().count()
+                })
+                .collect(),
+            //a glob matches the whole name, there is nothing to point out
+            FilterMode::Glob => Vec::new(),
+        }
+    }
+}
+
+// the text split into spans with the matched characters highlighted
+pub fn spans(text: &str, indices: &[usize], style: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    for (index, c) in text.chars().enumerate() {
+        let matched = indices.contains(&index);
+        if matched != current_matched && !current.is_empty() {
+            spans.push(styled(std::mem::take(&mut current), current_matched, style));
+        }
+        current_matched = matched;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        spans.push(styled(current, current_matched, style));
+    }
+    spans
+}
+
+fn styled(text: String, matched: bool, style: Style) -> Span<'static> {
+    if matched {
+        Span::styled(text, style.patch(MATCH_STYLE))
+    } else {
+        Span::styled(text, style)
+    }
+}
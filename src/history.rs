@@ -0,0 +1,31 @@
+use std::path::{Path, PathBuf};
+
+// how many directories the history popup lists
+const MAX_RECENT: usize = 50;
+
+// the recently visited directories of all tabs, the newest first
+pub struct History {
+    directories: Vec<PathBuf>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        History {
+            directories: Vec::new(),
+        }
+    }
+
+    //move the directory to the front, every directory is listed once
+    pub fn visit(&mut self, directory: &Path) {
+        if self.directories.first().map(PathBuf::as_path) == Some(directory) {
+            return;
+        }
+        self.directories.retain(|visited| visited != directory);
+        self.directories.insert(0, directory.to_path_buf());
+        self.directories.truncate(MAX_RECENT);
+    }
+
+    pub fn directories(&self) -> &[PathBuf] {
+        &self.directories
+    }
+}
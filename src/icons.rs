@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+// the glyphs need a Nerd Font, that's why icons are off by default
+const DIRECTORY: &str = "\u{f115}";
+const SYMLINK: &str = "\u{f0c1}";
+const FILE: &str = "\u{f016}";
+
+// well-known file names, checked before the extension
+const FILENAMES: &[(&str, &str)] = &[
+    ("Cargo.toml", "\u{e7a8}"),
+    ("Cargo.lock", "\u{e7a8}"),
+    ("Makefile", "\u{e779}"),
+    ("CMakeLists.txt", "\u{e779}"),
+    ("Dockerfile", "\u{e7b0}"),
+    ("docker-compose.yml", "\u{e7b0}"),
+    (".gitignore", "\u{e702}"),
+    (".gitmodules", "\u{e702}"),
+    (".gitattributes", "\u{e702}"),
+    ("package.json", "\u{e71e}"),
+    ("flake.nix", "\u{f313}"),
+    ("LICENSE", "\u{f02d}"),
+    ("README.md", "\u{f02d}"),
+    (".bashrc", "\u{f489}"),
+    (".zshrc", "\u{f489}"),
+];
+
+// extensions in lowercase
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "\u{e7a8}"),
+    ("py", "\u{e606}"),
+    ("js", "\u{e74e}"),
+    ("ts", "\u{e628}"),
+    ("go", "\u{e626}"),
+    ("c", "\u{e61e}"),
+    ("h", "\u{e61e}"),
+    ("cpp", "\u{e61d}"),
+    ("hpp", "\u{e61d}"),
+    ("java", "\u{e738}"),
+    ("lua", "\u{e620}"),
+    ("vim", "\u{e62b}"),
+    ("nix", "\u{f313}"),
+    ("html", "\u{e736}"),
+    ("css", "\u{e749}"),
+    ("sh", "\u{f489}"),
+    ("bash", "\u{f489}"),
+    ("zsh", "\u{f489}"),
+    ("fish", "\u{f489}"),
+    ("md", "\u{e609}"),
+    ("txt", "\u{f15c}"),
+    ("pdf", "\u{f1c1}"),
+    ("json", "\u{e60b}"),
+    ("toml", "\u{e615}"),
+    ("yml", "\u{e615}"),
+    ("yaml", "\u{e615}"),
+    ("lock", "\u{f023}"),
+    ("png", "\u{f1c5}"),
+    ("jpg", "\u{f1c5}"),
+    ("jpeg", "\u{f1c5}"),
+    ("gif", "\u{f1c5}"),
+    ("svg", "\u{f1c5}"),
+    ("webp", "\u{f1c5}"),
+    ("mp3", "\u{f001}"),
+    ("flac", "\u{f001}"),
+    ("wav", "\u{f001}"),
+    ("mp4", "\u{f03d}"),
+    ("mkv", "\u{f03d}"),
+    ("webm", "\u{f03d}"),
+    ("zip", "\u{f410}"),
+    ("tar", "\u{f410}"),
+    ("gz", "\u{f410}"),
+    ("xz", "\u{f410}"),
+    ("zst", "\u{f410}"),
+    ("7z", "\u{f410}"),
+];
+
+// icons from the config that replace or extend the built-in ones
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct IconOverrides {
+    pub directory: Option<String>,
+    pub symlink: Option<String>,
+    pub file: Option<String>,
+    pub filenames: HashMap<String, String>,
+    pub extensions: HashMap<String, String>,
+}
+
+// the icon for an entry, the overrides win over the built-in icons
+pub fn icon_for(entry: &Path, overrides: &IconOverrides) -> String {
+    if entry.is_symlink() {
+        return overrides.symlink.as_deref().unwrap_or(SYMLINK).to_string();
+    }
+    if entry.is_dir() {
+        return overrides
+            .directory
+            .as_deref()
+            .unwrap_or(DIRECTORY)
+            .to_string();
+    }
+
+    let name = entry.file_name().unwrap_or_default().to_string_lossy();
+    if let Some(icon) = overrides.filenames.get(name.as_ref()) {
+        return icon.clone();
+    }
+    if let Some((_, icon)) = FILENAMES.iter().find(|(known, _)| *known == name) {
+        return icon.to_string();
+    }
+
+    let extension = entry
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if let Some(icon) = overrides.extensions.get(&extension) {
+        return icon.clone();
+    }
+    if let Some((_, icon)) = EXTENSIONS.iter().find(|(known, _)| *known == extension) {
+        return icon.to_string();
+    }
+
+    overrides.file.as_deref().unwrap_or(FILE).to_string()
+}
@@ -0,0 +1,846 @@
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::Deserialize;
+
+use crate::file_list::PaneLayout;
+
+// everything a key of the normal mode can do
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Quit,
+    Help,
+    CommandLine,
+    CommandPalette,
+    MoveDown,
+    MoveUp,
+    Bottom,
+    Top,
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    ScreenTop,
+    ScreenMiddle,
+    ScreenBottom,
+    GoUp,
+    Open,
+    Home,
+    Back,
+    Forward,
+    PreviousDirectory,
+    History,
+    GoToPath,
+    Jump,
+    Find,
+    FuzzySearch,
+    Search,
+    RegexSearch,
+    GlobSearch,
+    Grep,
+    Rename,
+    Move,
+    Copy,
+    Delete,
+    Create,
+    ToggleSelection,
+    SelectPattern,
+    DeselectPattern,
+    InvertSelection,
+    SelectAll,
+    ToggleHidden,
+    ToggleDetails,
+    CycleSort,
+    ReverseSort,
+    CycleLayout,
+    SwitchPane,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PreviousTab,
+    SetBookmark,
+    JumpToBookmark,
+    Bookmarks,
+}
+
+// the name for the config, the group and description for the help and the default keys
+pub struct ActionInfo {
+    pub action: Action,
+    pub name: &'static str,
+    pub group: &'static str,
+    pub description: &'static str,
+    default_keys: &'static [&'static str],
+}
+
+const fn info(
+    action: Action,
+    name: &'static str,
+    group: &'static str,
+    description: &'static str,
+    default_keys: &'static [&'static str],
+) -> ActionInfo {
+    ActionInfo {
+        action,
+        name,
+        group,
+        description,
+        default_keys,
+    }
+}
+
+// every action in the order of the help
+pub const ACTIONS: &[ActionInfo] = &[
+    info(Action::Quit, "quit", "General", "quit zfile", &["q"]),
+    info(Action::Help, "help", "General", "show this help", &["?"]),
+    info(
+        Action::CommandLine,
+        "command_line",
+        "General",
+        "run a command like :cd ~/src or any action by its name",
+        &[":"],
+    ),
+    info(
+        Action::CommandPalette,
+        "command_palette",
+        "General",
+        "search all actions and run one",
+        &["<C-p>"],
+    ),
+    info(
+        Action::MoveDown,
+        "move_down",
+        "Navigation",
+        "move down",
+        &["j"],
+    ),
+    info(Action::MoveUp, "move_up", "Navigation", "move up", &["k"]),
+    info(
+        Action::Bottom,
+        "bottom",
+        "Navigation",
+        "go to the last entry, or to entry N with a count",
+        &["J", "G"],
+    ),
+    info(
+        Action::Top,
+        "top",
+        "Navigation",
+        "go to the first entry, or to entry N with a count",
+        &["K", "gg"],
+    ),
+    info(
+        Action::HalfPageDown,
+        "half_page_down",
+        "Navigation",
+        "scroll down half a screen",
+        &["<C-d>"],
+    ),
+    info(
+        Action::HalfPageUp,
+        "half_page_up",
+        "Navigation",
+        "scroll up half a screen",
+        &["<C-u>"],
+    ),
+    info(
+        Action::PageDown,
+        "page_down",
+        "Navigation",
+        "scroll down a screen",
+        &["<C-f>"],
+    ),
+    info(
+        Action::PageUp,
+        "page_up",
+        "Navigation",
+        "scroll up a screen",
+        &["<C-b>"],
+    ),
+    info(
+        Action::ScreenTop,
+        "screen_top",
+        "Navigation",
+        "go to the first entry on the screen",
+        &["H"],
+    ),
+    info(
+        Action::ScreenMiddle,
+        "screen_middle",
+        "Navigation",
+        "go to the entry in the middle of the screen",
+        &["M"],
+    ),
+    info(
+        Action::ScreenBottom,
+        "screen_bottom",
+        "Navigation",
+        "go to the last entry on the screen",
+        &["L"],
+    ),
+    info(
+        Action::GoUp,
+        "go_up",
+        "Navigation",
+        "go to the parent directory",
+        &["h"],
+    ),
+    info(
+        Action::Open,
+        "open",
+        "Navigation",
+        "enter the directory or open the file in nvim",
+        &["l"],
+    ),
+    info(
+        Action::Home,
+        "home",
+        "Navigation",
+        "go to the directory zfile started in",
+        &["gh"],
+    ),
+    info(
+        Action::Back,
+        "back",
+        "Navigation",
+        "go back in the history",
+        &["<C-o>"],
+    ),
+    info(
+        Action::PreviousDirectory,
+        "previous_directory",
+        "Navigation",
+        "go to the previous directory",
+        &["-"],
+    ),
+    info(
+        Action::History,
+        "history",
+        "Navigation",
+        "list the recent directories",
+        &["_"],
+    ),
+    info(
+        Action::GoToPath,
+        "go_to_path",
+        "Navigation",
+        "type the path of a directory to go to",
+        &["gp"],
+    ),
+    info(
+        Action::Jump,
+        "jump",
+        "Navigation",
+        "jump to a frecent directory",
+        &["z"],
+    ),
+    info(
+        Action::Find,
+        "find",
+        "Navigation",
+        "find files in the whole tree",
+        &["F"],
+    ),
+    info(
+        Action::FuzzySearch,
+        "fuzzy_search",
+        "Search",
+        "fuzzy search the names",
+        &["f"],
+    ),
+    info(
+        Action::Search,
+        "search",
+        "Search",
+        "search the names",
+        &["s"],
+    ),
+    info(
+        Action::RegexSearch,
+        "regex_search",
+        "Search",
+        "filter the names with a regex",
+        &["/"],
+    ),
+    info(
+        Action::GlobSearch,
+        "glob_search",
+        "Search",
+        "filter the names with a glob",
+        &["*"],
+    ),
+    info(
+        Action::Grep,
+        "grep",
+        "Search",
+        "search the contents of the files",
+        &["S"],
+    ),
+    info(
+        Action::Rename,
+        "rename",
+        "Files",
+        "rename the entry",
+        &["r"],
+    ),
+    info(Action::Move, "move", "Files", "move the entry", &["X"]),
+    info(Action::Copy, "copy", "Files", "copy the entry", &["yy"]),
+    info(
+        Action::Delete,
+        "delete",
+        "Files",
+        "delete the entry",
+        &["dd"],
+    ),
+    info(Action::Create, "create", "Files", "create a file", &["c"]),
+    info(
+        Action::ToggleSelection,
+        "toggle_selection",
+        "Selection",
+        "select the entry",
+        &["<Space>"],
+    ),
+    info(
+        Action::SelectPattern,
+        "select_pattern",
+        "Selection",
+        "select by a glob or /regex",
+        &["+"],
+    ),
+    info(
+        Action::DeselectPattern,
+        "deselect_pattern",
+        "Selection",
+        "deselect by a glob or /regex",
+        &["u"],
+    ),
+    info(
+        Action::InvertSelection,
+        "invert_selection",
+        "Selection",
+        "invert the selection",
+        &["~"],
+    ),
+    info(
+        Action::SelectAll,
+        "select_all",
+        "Selection",
+        "select all entries",
+        &["A"],
+    ),
+    info(
+        Action::ToggleHidden,
+        "toggle_hidden",
+        "View",
+        "show or hide hidden entries",
+        &["."],
+    ),
+    info(
+        Action::ToggleDetails,
+        "toggle_details",
+        "View",
+        "switch the detailed view",
+        &["i"],
+    ),
+    info(
+        Action::CycleSort,
+        "cycle_sort",
+        "View",
+        "sort by name, size or date",
+        &["o"],
+    ),
+    info(
+        Action::ReverseSort,
+        "reverse_sort",
+        "View",
+        "reverse the sort order",
+        &["O"],
+    ),
+    info(
+        Action::CycleLayout,
+        "cycle_layout",
+        "View",
+        "switch between the single, dual and miller layout",
+        &["v"],
+    ),
+    info(
+        Action::SwitchPane,
+        "switch_pane",
+        "View",
+        "focus the other pane of the dual layout",
+        &["<Tab>"],
+    ),
+    //after switch_pane so Tab switches the panes in the dual layout and goes forward otherwise,
+    //<C-n> goes forward in every layout
+    info(
+        Action::Forward,
+        "forward",
+        "Navigation",
+        "go forward in the history, in the dual layout only <C-n>",
+        &["<Tab>", "<C-n>"],
+    ),
+    info(Action::NewTab, "new_tab", "Tabs", "open a tab", &["t"]),
+    info(
+        Action::CloseTab,
+        "close_tab",
+        "Tabs",
+        "close the tab",
+        &["x"],
+    ),
+    info(
+        Action::NextTab,
+        "next_tab",
+        "Tabs",
+        "go to the next tab, or to tab N with a count",
+        &["gt"],
+    ),
+    info(
+        Action::PreviousTab,
+        "previous_tab",
+        "Tabs",
+        "go to the previous tab",
+        &["gT"],
+    ),
+    info(
+        Action::SetBookmark,
+        "set_bookmark",
+        "Bookmarks",
+        "bookmark the directory, followed by a letter",
+        &["m"],
+    ),
+    info(
+        Action::JumpToBookmark,
+        "jump_to_bookmark",
+        "Bookmarks",
+        "jump to a bookmark, followed by its letter",
+        &["'"],
+    ),
+    info(
+        Action::Bookmarks,
+        "bookmarks",
+        "Bookmarks",
+        "list, edit and delete the bookmarks",
+        &["B"],
+    ),
+];
+
+// the keys of the other modes, they are not remappable
+const MODE_KEYS: &[(&str, &str, &str)] = &[
+    (
+        "Navigation",
+        "<count>",
+        "repeat a motion like 10j or give it a number like 3gt",
+    ),
+    ("Tabs", "<A-1>..<A-9>", "go to the tab with the number"),
+    ("Search", "<Esc>", "clear the search filter"),
+    ("Search bar", "<Enter>", "open the selected entry"),
+    ("Search bar", "<Esc>", "stop searching"),
+    ("Prompts", "<Tab>", "complete the path"),
+    ("Prompts", "<Up>/<Down>", "choose a completion"),
+    ("Prompts", "<Enter>", "confirm"),
+    ("Prompts", "<Esc>", "cancel"),
+    ("Popups", "<Up>/<Down>, <C-n>/<C-p>", "move the selection"),
+    ("Popups", "<Enter>", "go to the selected entry"),
+    ("Popups", "<C-e>/<C-d>", "edit or delete the bookmark"),
+    (
+        "Popups",
+        "<C-r>",
+        "switch the content search between literal and regex",
+    ),
+    ("Popups", "<Esc>", "close"),
+];
+
+impl Action {
+    fn info(self) -> Option<&'static ActionInfo> {
+        ACTIONS.iter().find(|info| info.action == self)
+    }
+
+    pub fn name(self) -> &'static str {
+        self.info().map_or("", |info| info.name)
+    }
+
+    pub fn description(self) -> &'static str {
+        self.info().map_or("", |info| info.description)
+    }
+
+    //switching the pane only works in the dual layout
+    pub fn is_available(self, layout: PaneLayout) -> bool {
+        self != Action::SwitchPane || layout == PaneLayout::Dual
+    }
+}
+
+// the available actions matching the query of the command palette, the best first
+pub fn palette(query: &str, layout: PaneLayout, matcher: &SkimMatcherV2) -> Vec<Action> {
+    let mut scored: Vec<(i64, Action)> = ACTIONS
+        .iter()
+        .filter(|info| info.action != Action::CommandPalette && info.action.is_available(layout))
+        .filter_map(|info| {
+            //the name and the description so "hidden" finds "show or hide hidden entries"
+            let text = format!("{} {}", info.name.replace('_', " "), info.description);
+            matcher
+                .fuzzy_match(&text, query)
+                .map(|score| (score, info.action))
+        })
+        .collect();
+    //an empty query keeps the order of the help
+    if !query.is_empty() {
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    }
+    scored.into_iter().map(|(_, action)| action).collect()
+}
+
+// one key press, shift is part of the character
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn from_event(event: KeyEvent) -> Key {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        //ctrl-i arrives as tab in most terminals
+        if event.code == KeyCode::Tab {
+            modifiers = KeyModifiers::NONE;
+        }
+        Key {
+            code: event.code,
+            modifiers,
+        }
+    }
+
+    //"j", "gT", "<C-o>" or "<Space>" as the keys to press one after another
+    pub fn parse_sequence(text: &str) -> Option<Vec<Key>> {
+        let mut keys = Vec::new();
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let special = rest
+                .strip_prefix('<')
+                .and_then(|inner| inner.split_once('>'))
+                .and_then(|(name, after)| Some((parse_special(name)?, after)));
+            match special {
+                Some((key, after)) => {
+                    keys.push(key);
+                    rest = after;
+                }
+                None => {
+                    keys.push(Key {
+                        code: KeyCode::Char(c),
+                        modifiers: KeyModifiers::NONE,
+                    });
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        (!keys.is_empty()).then_some(keys)
+    }
+
+    pub fn label(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.modifiers.is_empty() => return c.to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Left => "Left".to_string(),
+            KeyCode::Right => "Right".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::F(number) => format!("F{}", number),
+            _ => "?".to_string(),
+        };
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("C-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("A-");
+        }
+        format!("<{}{}>", prefix, name)
+    }
+}
+
+// the name between '<' and '>', e.g. "C-o", "Space" or "Enter"
+fn parse_special(name: &str) -> Option<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = name;
+    loop {
+        if let Some(rest) = name.strip_prefix("C-") {
+            modifiers |= KeyModifiers::CONTROL;
+            name = rest;
+        } else if let Some(rest) = name.strip_prefix("A-") {
+            modifiers |= KeyModifiers::ALT;
+            name = rest;
+        } else {
+            break;
+        }
+    }
+    let code = match name.to_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "s-tab" => KeyCode::BackTab,
+        "enter" | "cr" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "bs" => KeyCode::Backspace,
+        "del" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "lt" => KeyCode::Char('<'),
+        "gt" => KeyCode::Char('>'),
+        lower => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                //a single character keeps its case, <C-o> and <C-O> differ
+                (Some(c), None) if !modifiers.is_empty() => KeyCode::Char(c),
+                _ => {
+                    let number = lower.strip_prefix('f')?.parse().ok()?;
+                    KeyCode::F(number)
+                }
+            }
+        }
+    };
+    Some(Key { code, modifiers })
+}
+
+pub fn sequence_label(keys: &[Key]) -> String {
+    keys.iter().map(Key::label).collect()
+}
+
+// one key sequence or a list of them in the config
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+// what the keys pressed so far lead to
+pub enum Lookup {
+    //the actions bound to exactly these keys, the first available one runs
+    Actions(Vec<Action>),
+    //a command line from the [commands] of the config like "cd ~/src"
+    Command(String),
+    //more keys are needed
+    Prefix,
+    //nothing is bound to these keys
+    None,
+}
+
+// the bindings of the normal mode, the defaults with the [keys] of the config
+pub struct Keymap {
+    bindings: Vec<(Vec<Key>, Action)>,
+    //the key sequences that run a command line, they win over the actions
+    commands: Vec<(Vec<Key>, String)>,
+}
+
+impl Keymap {
+    //the keys of an action in the config replace its default keys
+    pub fn new(overrides: &HashMap<String, KeyList>, commands: &HashMap<String, String>) -> Keymap {
+        let mut bindings = Vec::new();
+        for info in ACTIONS {
+            let keys: Vec<&str> = match overrides.get(info.name) {
+                Some(KeyList::One(keys)) => vec![keys.as_str()],
+                Some(KeyList::Many(keys)) => keys.iter().map(String::as_str).collect(),
+                None => info.default_keys.to_vec(),
+            };
+            for keys in keys {
+                if let Some(sequence) = Key::parse_sequence(keys) {
+                    bindings.push((sequence, info.action));
+                }
+            }
+        }
+        let mut commands: Vec<(Vec<Key>, String)> = commands
+            .iter()
+            .filter_map(|(keys, command)| Some((Key::parse_sequence(keys)?, command.clone())))
+            .collect();
+        commands.sort_by_key(|(sequence, _)| sequence_label(sequence));
+        Keymap { bindings, commands }
+    }
+
+    //the keys that can follow the pressed ones with what they do, for the hints
+    pub fn continuations(&self, keys: &[Key]) -> Vec<(String, String)> {
+        let commands = self
+            .commands
+            .iter()
+            .map(|(sequence, command)| (sequence, format!(":{}", command)));
+        let actions = self
+            .bindings
+            .iter()
+            .map(|(sequence, action)| (sequence, action.description().to_string()));
+        let mut continuations: Vec<(String, String)> = Vec::new();
+        for (sequence, description) in commands.chain(actions) {
+            if sequence.len() > keys.len() && sequence.starts_with(keys) {
+                let label = sequence_label(&sequence[keys.len()..]);
+                //a sequence bound to more than one thing runs the first available one
+                if !continuations.iter().any(|(found, _)| *found == label) {
+                    continuations.push((label, description));
+                }
+            }
+        }
+        continuations
+    }
+
+    //a sequence that is the start of a longer one waits for more keys
+    pub fn lookup(&self, keys: &[Key]) -> Lookup {
+        let longer = self
+            .bindings
+            .iter()
+            .map(|(sequence, _)| sequence)
+            .chain(self.commands.iter().map(|(sequence, _)| sequence))
+            .any(|sequence| sequence.len() > keys.len() && sequence.starts_with(keys));
+        if longer {
+            return Lookup::Prefix;
+        }
+        if let Some((_, command)) = self.commands.iter().find(|(sequence, _)| sequence == keys) {
+            return Lookup::Command(command.clone());
+        }
+        let actions: Vec<Action> = self
+            .bindings
+            .iter()
+            .filter(|(sequence, _)| sequence == keys)
+            .map(|(_, action)| *action)
+            .collect();
+        if actions.is_empty() {
+            Lookup::None
+        } else {
+            Lookup::Actions(actions)
+        }
+    }
+
+    //the keys bound to the action like "gt, <C-n>"
+    pub fn keys_for(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(sequence, _)| sequence_label(sequence))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    //every binding of the normal mode first, then the keys of the other modes,
+    //the groups kept together in the order they first show up
+    pub fn help(&self) -> Vec<HelpLine> {
+        let mut help: Vec<HelpLine> = ACTIONS
+            .iter()
+            .map(|info| HelpLine {
+                group: info.group,
+                keys: self.keys_for(info.action),
+                description: info.description.to_string(),
+                name: info.name,
+            })
+            .filter(|line| !line.keys.is_empty())
+            .collect();
+        help.extend(self.commands.iter().map(|(sequence, command)| HelpLine {
+            group: "Commands",
+            keys: sequence_label(sequence),
+            description: format!(":{}", command),
+            name: "",
+        }));
+        help.extend(MODE_KEYS.iter().map(|(group, keys, description)| HelpLine {
+            group,
+            keys: keys.to_string(),
+            description: description.to_string(),
+            name: "",
+        }));
+        let mut groups: Vec<&str> = Vec::new();
+        for line in &help {
+            if !groups.contains(&line.group) {
+                groups.push(line.group);
+            }
+        }
+        help.sort_by_key(|line| groups.iter().position(|group| *group == line.group));
+        help
+    }
+}
+
+// one line of the help
+pub struct HelpLine {
+    pub group: &'static str,
+    pub keys: String,
+    pub description: String,
+    //the name of the action for the config and the command line, empty for the other keys
+    pub name: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        Key { code, modifiers }
+    }
+
+    fn char_key(c: char) -> Key {
+        key(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn plain_characters_are_one_key_each() {
+        assert_eq!(
+            Key::parse_sequence("gT"),
+            Some(vec![char_key('g'), char_key('T')])
+        );
+        assert_eq!(Key::parse_sequence(""), None);
+    }
+
+    #[test]
+    fn special_keys_and_modifiers_are_in_angle_brackets() {
+        assert_eq!(
+            Key::parse_sequence("<C-d>"),
+            Some(vec![key(KeyCode::Char('d'), KeyModifiers::CONTROL)])
+        );
+        assert_eq!(
+            Key::parse_sequence("<A-1>"),
+            Some(vec![key(KeyCode::Char('1'), KeyModifiers::ALT)])
+        );
+        assert_eq!(
+            Key::parse_sequence("<C-A-x>"),
+            Some(vec![key(
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            )])
+        );
+        assert_eq!(
+            Key::parse_sequence("<Space><Tab><F5>"),
+            Some(vec![
+                char_key(' '),
+                key(KeyCode::Tab, KeyModifiers::NONE),
+                key(KeyCode::F(5), KeyModifiers::NONE),
+            ])
+        );
+        assert_eq!(Key::parse_sequence("<lt>"), Some(vec![char_key('<')]));
+    }
+
+    #[test]
+    fn an_unknown_name_in_brackets_is_typed_literally() {
+        assert_eq!(
+            Key::parse_sequence("<x>"),
+            Some(vec![char_key('<'), char_key('x'), char_key('>')])
+        );
+        assert_eq!(
+            Key::parse_sequence("<C-"),
+            Some(vec![char_key('<'), char_key('C'), char_key('-')])
+        );
+    }
+
+    #[test]
+    fn labels_read_like_the_config() {
+        let sequence = Key::parse_sequence("g<C-o><Space>").unwrap();
+        assert_eq!(sequence_label(&sequence), "g<C-o><Space>");
+    }
+}
@@ -1,27 +1,79 @@
 // for handling the terminal with user input
+mod bookmarks;
+mod colors;
+mod command;
+mod config;
+mod details;
+mod entry_name;
+mod file_list;
 mod file_manipulation;
+mod filter;
+mod finder;
+mod frecency;
+mod git_status;
+mod grep;
+mod highlight;
+mod history;
+mod icons;
+mod keymap;
+mod path_input;
+mod popup;
+mod query;
+mod sort;
+mod tab;
 
 // for input handling
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 // for standard input/output
-use std::process::{Command, Stdio};
+use std::process::{self, Stdio};
 // for the ui components
 use ratatui::{
     Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Style},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, ListItem, Paragraph, Tabs, Wrap},
 };
 // for getting the data from the file system
-use std::{fs, io, path::PathBuf};
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// for hiding dotfiles and ignored entries
+use config::Config;
+use globset::GlobSet;
+use ignore::WalkBuilder;
+
+// for the owner column of the detailed view
+use uzers::UsersCache;
+
+// every tab holds its own directory, selection, search and sorting
+use tab::{CursorMemory, Tab};
+
+// for rendering the file lists of the different layouts
+use entry_name::NameContext;
+use file_list::{FileListView, Pane, PaneLayout};
+use filter::FilterMode;
+
+// the remappable keys of the normal mode
+use keymap::{Action, Key, Keymap, Lookup, sequence_label};
+
+// the command line
+use command::{Command, Flag};
 
 // fuzzy matching
-use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 
 // different modes like in nvim
@@ -29,35 +81,142 @@ enum InputMode {
     Normal,
     Rename,
     Move,
+    Copy,
     DeleteConfirm,
     Create,
+    History,
+    Bookmarks,
+    BookmarkEdit,
+    Jump,
+    GoTo,
+    Find,
+    Grep,
+    SelectPattern,
+    DeselectPattern,
+    Help,
+    ContextMenu,
+    Command,
+    Palette,
 }
 
+// the file operations of the right click menu
+const MENU_ACTIONS: &[Action] = &[
+    Action::Open,
+    Action::Rename,
+    Action::Copy,
+    Action::Move,
+    Action::Delete,
+    Action::Create,
+    Action::ToggleSelection,
+];
+
+// two clicks on the same entry within this time open it
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+// how many lines the scroll wheel moves
+const SCROLL_LINES: usize = 3;
+// a longer count like "999999j" stops growing, no list is that long
+const MAX_COUNT: usize = 99999;
+
 fn main() -> Result<(), io::Error> {
     //------------------------------------------------------------------------------
     //
     //  INITIALIZATION
     //
     //------------------------------------------------------------------------------
+    //the visited directories for jumping with 'z'
+    let mut frecency = frecency::Frecency::load();
+
+    //"zfile --import zoxide" or "zfile --import autojump" fills the database and exits
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("--import") {
+        let source = args.get(2).map(String::as_str).unwrap_or("zoxide");
+        match frecency.import(source) {
+            Ok(count) => println!("imported {} directories from {}", count, source),
+            Err(error) => {
+                eprintln!("could not import from {}: {}", source, error);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    //the user config
+    let config = config::load_config();
+
     //entering an alternaate screen and enabling raw mode
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
-    let mut terminal = Some(init_terminal()?);
+    let mut terminal = Some(init_terminal(config.mouse)?);
+
+    //the compiled ignore globs from the config
+    let ignore_globs = Arc::new(config.ignore_globset());
+    //if hidden and ignored entries are shown
+    let mut show_hidden: bool = config.show_hidden;
+    //if the detailed list view with size, mode, owner and mtime is active
+    let mut detailed_view: bool = config.detailed_view;
+    //cache for the user and group names of the detailed view
+    let users = UsersCache::new();
+    //the colors for the entries from LS_COLORS or the built-in theme
+    let ls_colors = colors::load_ls_colors();
+    //the git status of the current directory, refreshed when it changes
+    let mut git_cache = git_status::GitCache::new();
+    //the same for the other pane of the dual layout
+    let mut other_git_cache = git_status::GitCache::new();
 
-    //current directory
-    let mut current_directory: PathBuf = std::env::current_dir()?;
     //the directory from where the program starts
-    let root_dir: PathBuf = current_directory.clone();
-    //the currently selected file
-    let mut selected_file: usize = 0;
+    let root_dir: PathBuf = std::env::current_dir()?;
+    //the open tabs, each with its own directory, selection, search and sorting
+    let mut tabs: Vec<Tab> = vec![Tab::new(root_dir.clone(), &config)];
+    //the index of the tab that is shown
+    let mut active_tab: usize = 0;
+    //the selected entry of every visited directory to put the cursor back
+    let mut cursor_memory: CursorMemory = CursorMemory::new();
+    //the recently visited directories for the history popup
+    let mut history = history::History::new();
+    //the selected entry of a popup
+    let mut popup_selected: usize = 0;
+    //the named directories set with m<char>
+    let mut bookmarks = bookmarks::Bookmarks::load();
+    //the filter of the bookmark popup
+    let mut bookmark_query = String::new();
+    //the key of the bookmark that is edited in the popup
+    let mut editing_bookmark: Option<char> = None;
+    //the recursive finder while it is open
+    let mut finder: Option<finder::Finder> = None;
+    //the content search while it is open
+    let mut grep: Option<grep::Grep> = None;
+    //if the content search pattern is a regex instead of a literal
+    let mut grep_regex: bool = false;
+    //what the last command did, shown in the footer until the next key
+    let mut message: Option<String> = None;
+    //the keys of the normal mode and what they do
+    let keymap = Keymap::new(&config.keys, &config.commands);
+    //the keys of a sequence like "gt" pressed so far
+    let mut pending_keys: Vec<Key> = Vec::new();
+    //the command lines that ran before and how far back Up went in them
+    let mut command_history = command::CommandHistory::load();
+    let mut history_back: Option<usize> = None;
+    //the count typed before a motion like the 10 of "10j"
+    let mut count: Option<usize> = None;
+    //the time and the entry of the last click to notice double clicks
+    let mut last_click: Option<(Instant, usize)> = None;
+    //the corner of the right click menu
+    let mut menu_position: (u16, u16) = (0, 0);
+    //how far the preview is scrolled with the wheel and for which entry
+    let mut preview_scroll: u16 = 0;
+    let mut preview_scrolled: Option<PathBuf> = None;
+    //when the last of the pending keys was pressed, the hints show up after a delay
+    let mut pending_since = Instant::now();
+    //'m' or '\'' waiting for the letter of the bookmark
+    let mut awaiting_bookmark: Option<Action> = None;
 
-    //the query string when searching through the files
-    let mut query: String = String::new();
-    //if the search bar is active
-    let mut in_search: bool = false;
-    //if the fuzzy search is active
-    let mut fuzzy_mode: bool = false;
+    //single (list and preview) or dual (two file lists)
+    let mut pane_layout: PaneLayout = config.layout;
+    //the unfocused file list of the dual layout, swapped with the active tab on focus change
+    let mut other_pane: Tab = Tab::new(root_dir.clone(), &config);
+    //if the focused pane is the right one in the dual layout
+    let mut focus_right: bool = false;
 
     //the current mode of the program
     let mut input_mode = InputMode::Normal;
@@ -71,161 +230,769 @@ fn main() -> Result<(), io::Error> {
 
     //the main loop that recursively runs until user presses 'q'
     loop {
-        //get the entries from the current directory but unfiltered
-        let mut entries_raw = get_entries(&current_directory);
-
-        //filter the entries based on the query
-        let entries: Vec<PathBuf> = if query.is_empty() {
-            entries_raw
-        } else if fuzzy_mode {
-            let q = query.clone();
-            let mut scored: Vec<(PathBuf, i64)> = entries_raw
-                .drain(..)
-                .filter_map(|p| {
-                    let name = p
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-                    matcher.fuzzy_match(&name, &q).map(|score| (p, score))
-                })
-                .collect();
-            scored.sort_by(|a, b| b.1.cmp(&a.1));
-            scored.into_iter().map(|(p, _)| p).collect()
+        //the titles for the tab bar, taken before the active tab is borrowed
+        let tab_titles: Vec<String> = tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| format!("{} {}", i + 1, tab.title()))
+            .collect();
+        //the tab that is shown and receives the keys
+        let tab = &mut tabs[active_tab];
+        history.visit(&tab.current_directory);
+        frecency.visit(&tab.current_directory);
+        //the actions of the command palette matching its query
+        let palette_matches: Vec<Action> = match input_mode {
+            InputMode::Palette => {
+                let palette_matches = keymap::palette(&input_buffer, pane_layout, &matcher);
+                popup_selected = popup_selected.min(palette_matches.len().saturating_sub(1));
+                palette_matches
+            }
+            _ => Vec::new(),
+        };
+        //the bookmarks in the popup, filtered by its query
+        let bookmark_matches = match input_mode {
+            InputMode::Bookmarks | InputMode::BookmarkEdit => {
+                let bookmark_matches = bookmarks.filter(&bookmark_query, &matcher);
+                popup_selected = popup_selected.min(bookmark_matches.len().saturating_sub(1));
+                bookmark_matches
+            }
+            _ => Vec::new(),
+        };
+        //the keys in the help, searched by their keys, description and group
+        let help_lines = match input_mode {
+            InputMode::Help => {
+                let query = input_buffer.to_lowercase();
+                let help_lines: Vec<_> = keymap
+                    .help()
+                    .into_iter()
+                    .filter(|line| {
+                        [line.group, line.keys.as_str(), &line.description, line.name]
+                            .iter()
+                            .any(|text| text.to_lowercase().contains(&query))
+                    })
+                    .collect();
+                //a header line for every group
+                let mut groups: Vec<&str> = help_lines.iter().map(|line| line.group).collect();
+                groups.dedup();
+                popup_selected =
+                    popup_selected.min((help_lines.len() + groups.len()).saturating_sub(1));
+                help_lines
+            }
+            _ => Vec::new(),
+        };
+        //which keys can follow, shown when the next key takes a moment
+        let key_hints: Vec<(String, String)> = if matches!(input_mode, InputMode::Normal)
+            && pending_since.elapsed().as_millis() >= u128::from(config.key_hint_delay)
+        {
+            match awaiting_bookmark {
+                Some(Action::JumpToBookmark) => bookmarks
+                    .filter("", &matcher)
+                    .into_iter()
+                    .map(|(key, path)| (key.to_string(), path.to_string_lossy().to_string()))
+                    .collect(),
+                None if !pending_keys.is_empty() => {
+                    keymap.continuations(&pending_keys).into_iter().collect()
+                }
+                _ => Vec::new(),
+            }
         } else {
-            let q = query.to_lowercase();
-            entries_raw
-                .into_iter()
-                .filter(|p| {
-                    p.file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_lowercase()
-                        .contains(&q)
-                })
-                .collect()
+            Vec::new()
+        };
+        //the directories matching the jump query, the best first
+        let jump_matches: Vec<PathBuf> = match input_mode {
+            InputMode::Jump => {
+                let jump_matches = frecency.query(&input_buffer, &matcher);
+                popup_selected = popup_selected.min(jump_matches.len().saturating_sub(1));
+                jump_matches
+            }
+            _ => Vec::new(),
+        };
+        //the completions of the typed path, only directories when going to one
+        let completions: Vec<String> = match input_mode {
+            InputMode::GoTo | InputMode::Move | InputMode::Copy => {
+                let dirs_only = matches!(input_mode, InputMode::GoTo);
+                let completions =
+                    path_input::completions(&input_buffer, &tab.current_directory, dirs_only);
+                popup_selected = popup_selected.min(completions.len().saturating_sub(1));
+                completions
+            }
+            //the names of the commands and their arguments
+            InputMode::Command => {
+                let completions = command::completions(&input_buffer, &tab.current_directory);
+                popup_selected = popup_selected.min(completions.len().saturating_sub(1));
+                completions
+            }
+            _ => Vec::new(),
         };
+
+        //the paths of the recursive finder matching its query, relative to where it started
+        let find_matches: Vec<PathBuf> = match (&input_mode, finder.as_mut()) {
+            (InputMode::Find, Some(finder)) => {
+                let find_matches = finder.update(&input_buffer, &matcher).to_vec();
+                popup_selected = popup_selected.min(find_matches.len().saturating_sub(1));
+                find_matches
+            }
+            _ => Vec::new(),
+        };
+
+        //the matching lines of the content search, restarted when its pattern changes
+        if let InputMode::Grep = input_mode
+            && grep
+                .as_ref()
+                .is_none_or(|grep| grep.pattern != input_buffer || grep.regex != grep_regex)
+        {
+            grep = Some(grep::Grep::start(
+                tab.current_directory.clone(),
+                &input_buffer,
+                grep_regex,
+                show_hidden,
+                &config,
+                &ignore_globs,
+            ));
+        }
+        if let Some(grep) = grep.as_mut() {
+            grep.update();
+            popup_selected = popup_selected.min(grep.matches().len().saturating_sub(1));
+        }
+        let grep_matches: &[grep::GrepMatch] =
+            grep.as_ref().map(|grep| grep.matches()).unwrap_or(&[]);
+
+        //get the sorted entries of the tab filtered by its query
+        let (entries, hidden_count, filter_error) =
+            tab_entries(tab, show_hidden, &config, &ignore_globs, &matcher);
         //------------------------------------------------------------------------------
 
+        //select the remembered entry after changing the directory
+        if let Some(name) = tab.select_name.take()
+            && let Some(index) = entries
+                .iter()
+                .position(|entry| entry.file_name() == Some(name.as_os_str()))
+        {
+            tab.selected_file = index;
+        }
+
         //check if the list is empty
         if entries.is_empty() {
             //i think its unnecessary to set the selected file to 0 because it will be set to the last entry and that is 0
-            selected_file = 0;
-        } else if selected_file >= entries.len() {
-            selected_file = entries.len().saturating_sub(1);
+            tab.selected_file = 0;
+        } else if tab.selected_file >= entries.len() {
+            tab.selected_file = entries.len().saturating_sub(1);
+        }
+        //remember the selection for when we come back to this directory
+        if let Some(name) = entries
+            .get(tab.selected_file)
+            .and_then(|entry| entry.file_name())
+        {
+            cursor_memory.insert(tab.current_directory.clone(), name.to_os_string());
         }
+
+        //the entries of the other pane, only needed in the dual layout
+        let (other_entries, other_hidden_count) = if pane_layout == PaneLayout::Dual {
+            let (other_entries, other_hidden_count, _) = tab_entries(
+                &mut other_pane,
+                show_hidden,
+                &config,
+                &ignore_globs,
+                &matcher,
+            );
+            other_pane.selected_file = other_pane
+                .selected_file
+                .min(other_entries.len().saturating_sub(1));
+            (other_entries, other_hidden_count)
+        } else {
+            (Vec::new(), 0)
+        };
+        //the entries of the parent directory, only needed in the miller layout
+        let parent_entries: Vec<PathBuf> = match tab.current_directory.parent() {
+            Some(parent) if pane_layout == PaneLayout::Miller => {
+                let (mut parent_entries, _) =
+                    get_entries(&parent.to_path_buf(), show_hidden, &config, &ignore_globs);
+                sort::sort_entries(
+                    &mut parent_entries,
+                    tab.sort_mode,
+                    tab.sort_reverse,
+                    config.dirs_first,
+                );
+                parent_entries
+            }
+            _ => Vec::new(),
+        };
+        //the git status and branch, None outside of a repository
+        let git_info = if config.git_status {
+            git_cache.get(&tab.current_directory)
+        } else {
+            None
+        };
+        let other_git_info = if config.git_status && pane_layout == PaneLayout::Dual {
+            other_git_cache.get(&other_pane.current_directory)
+        } else {
+            None
+        };
+        //how the file lists get rendered
+        let view = FileListView {
+            config: &config,
+            users: &users,
+            detailed_view,
+        };
         //------------------------------------------------------------------------------
         //
         //  DRAWING
         //
         //------------------------------------------------------------------------------
 
+        //where the file lists ended up scrolled, kept for the next frame and the screen motions
+        let mut list_scroll = (tab.list_offset, tab.list_height);
+        let mut other_scroll = (other_pane.list_offset, other_pane.list_height);
+        //where the parts are on the screen for the mouse
+        let mut list_area = Rect::default();
+        let mut other_list_area = Rect::default();
+        let mut preview_rect: Option<Rect> = None;
+        let mut menu_area = Rect::default();
         if let Some(ref mut term) = terminal {
             //draw the ui
             term.draw(|f| {
-                //split the screen into two columns
-                let layout = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-                    //f is the frame
-                    .split(f.area());
-
-                //split the first column into two rows
+                //the single layout has the navigation on the left and the preview on the right,
+                //the dual and miller layouts use the full width for the navigation
+                let (nav_area, mut preview_area) = if pane_layout == PaneLayout::Single {
+                    //split the screen into two columns
+                    let layout = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                        //f is the frame
+                        .split(f.area());
+                    (layout[0], Some(layout[1]))
+                } else {
+                    (f.area(), None)
+                };
+
+                //split the navigation into rows
                 let nav_column = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([
+                        Constraint::Length(1), // tab bar
                         Constraint::Length(3), // search bar
                         Constraint::Min(0),    // file list
                         Constraint::Length(3), // mode display
                     ])
-                    .split(layout[0]);
+                    .split(nav_area);
+
+                //render the tab bar
+                let tab_bar = Tabs::new(tab_titles.clone())
+                    .select(active_tab)
+                    .highlight_style(
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    );
+                f.render_widget(tab_bar, nav_column[0]);
 
                 //the title of the search bar
-                let title = if fuzzy_mode {
-                    "Search (Fuzzy): type to filter, Esc to exit"
-                    //if the search bar is active and the fuzzy search is unactive
-                } else if in_search {
-                    "Search: type to filter, Esc to exit"
+                let label = tab.filter_mode.label();
+                let title = if let Some(error) = &filter_error {
+                    format!("{}: invalid pattern, {}", label, error)
+                } else if tab.in_search {
+                    format!("{}: type to filter, Esc to exit", label)
+                    //the filter stays while navigating after Enter with keep_filter
+                } else if !tab.query.is_empty() {
+                    format!("{}: kept while navigating, Esc to clear", label)
                     //if the search bar is unactive
                 } else {
-                    "Search (press 'f' for fuzzy, 's' for normal)"
+                    "Search (press 'f' for fuzzy, 's' for normal, '/' for regex, '*' for glob)"
+                        .to_string()
                 };
 
                 //render the search bar
-                let search_paragraph = Paragraph::new(query.as_str())
+                let search_paragraph = Paragraph::new(tab.query.as_str())
                     .block(Block::default().title(title).borders(Borders::ALL));
-                f.render_widget(search_paragraph, nav_column[0]);
-                //declare the items for the list
-                let items: Vec<ListItem> = entries
-                    .iter()
-                    .map(|entry| {
-                        let name = entry.file_name().unwrap_or_default().to_string_lossy();
-                        let display_name = if entry.is_dir() {
-                            format!("{}/", name)
-                        } else {
-                            name.to_string()
-                        };
-                        ListItem::new(display_name)
-                    })
-                    .collect();
+                f.render_widget(search_paragraph, nav_column[1]);
 
-                //declaring a "frame" for the list where it can be rendered
-                let ui_list = List::new(items)
-                    .block(Block::default().title("Files").borders(Borders::ALL))
-                    .highlight_style(Style::default().fg(Color::Cyan));
-
-                let mut list_state = ratatui::widgets::ListState::default();
-                if !entries.is_empty() {
-                    list_state.select(Some(selected_file));
+                let pane = Pane {
+                    tab,
+                    entries: &entries,
+                    hidden_count,
+                    name_context: NameContext {
+                        ls_colors: &ls_colors,
+                        icons: config.show_icons.then_some(&config.icons),
+                        git: git_info,
+                        highlight: search_highlight(tab, &matcher),
+                        selection: (!tab.selection.is_empty()).then_some(&tab.selection),
+                    },
+                    focused: true,
+                };
+                if pane_layout == PaneLayout::Miller {
+                    //split the file list into the parent, the current directory and the preview
+                    let [parent_ratio, list_ratio, preview_ratio] = config.miller_ratios;
+                    let columns = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([
+                            Constraint::Fill(parent_ratio),
+                            Constraint::Fill(list_ratio),
+                            Constraint::Fill(preview_ratio),
+                        ])
+                        .split(nav_column[2]);
+                    file_list::render_parent_list(
+                        f,
+                        columns[0],
+                        &tab.current_directory,
+                        &parent_entries,
+                        //the search only filters the current directory
+                        &NameContext {
+                            highlight: None,
+                            selection: None,
+                            ..pane.name_context
+                        },
+                    );
+                    list_area = columns[1];
+                    list_scroll = file_list::render_file_list(f, list_area, &pane, &view);
+                    preview_area = Some(columns[2]);
+                } else if pane_layout == PaneLayout::Dual {
+                    //split the file list into two panes, the focused one keeps its side
+                    let panes = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(nav_column[2]);
+                    let (focused_area, other_area) = if focus_right {
+                        (panes[1], panes[0])
+                    } else {
+                        (panes[0], panes[1])
+                    };
+                    let other = Pane {
+                        tab: &other_pane,
+                        entries: &other_entries,
+                        hidden_count: other_hidden_count,
+                        name_context: NameContext {
+                            ls_colors: &ls_colors,
+                            icons: config.show_icons.then_some(&config.icons),
+                            git: other_git_info,
+                            highlight: search_highlight(&other_pane, &matcher),
+                            selection: (!other_pane.selection.is_empty())
+                                .then_some(&other_pane.selection),
+                        },
+                        focused: false,
+                    };
+                    (list_area, other_list_area) = (focused_area, other_area);
+                    list_scroll = file_list::render_file_list(f, list_area, &pane, &view);
+                    other_scroll = file_list::render_file_list(f, other_area, &other, &view);
+                } else {
+                    list_area = nav_column[2];
+                    list_scroll = file_list::render_file_list(f, list_area, &pane, &view);
                 }
-                //render the list
-                f.render_stateful_widget(ui_list, nav_column[1], &mut list_state);
                 // footer text + color per mode
+                //the prompts of file operations say when they work on the selection
+                let selected_label = if tab.selection.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {} selected", tab.selection.len())
+                };
                 let (footer_text, footer_style) = match input_mode {
                     InputMode::Normal => {
-                        ("NORMAL MODE".to_string(), Style::default().fg(Color::White))
+                        let text = match &message {
+                            Some(message) => message.clone(),
+                            None if !tab.selection.is_empty() => {
+                                format!("NORMAL MODE ({} selected)", tab.selection.len())
+                            }
+                            None => "NORMAL MODE".to_string(),
+                        };
+                        //the count and keys typed so far like "10g"
+                        let typed = format!(
+                            "{}{}",
+                            count.map(|count| count.to_string()).unwrap_or_default(),
+                            sequence_label(&pending_keys)
+                        );
+                        let text = if typed.is_empty() {
+                            text
+                        } else {
+                            format!("{}  {}", text, typed)
+                        };
+                        (text, Style::default().fg(Color::White))
                     }
+                    InputMode::SelectPattern => (
+                        format!("SELECT (glob or /regex): {}", input_buffer),
+                        Style::default().fg(Color::LightYellow),
+                    ),
+                    InputMode::DeselectPattern => (
+                        format!("DESELECT (glob or /regex): {}", input_buffer),
+                        Style::default().fg(Color::LightYellow),
+                    ),
                     InputMode::Rename => (
                         format!("RENAME: {}", input_buffer),
                         Style::default().fg(Color::LightBlue),
                     ),
                     InputMode::Move => (
-                        format!("MOVE: {}", input_buffer),
+                        format!("MOVE{}: {}", selected_label, input_buffer),
                         Style::default().fg(Color::Magenta),
                     ),
-                    InputMode::DeleteConfirm => {
-                        ("DELETE? (y/n)".to_string(), Style::default().fg(Color::Red))
-                    }
+                    InputMode::Copy => (
+                        format!("COPY{}: {}", selected_label, input_buffer),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    InputMode::DeleteConfirm => (
+                        format!("DELETE{}? (y/n)", selected_label),
+                        Style::default().fg(Color::Red),
+                    ),
                     InputMode::Create => (
                         format!("CREATE: {}", create_buffer),
                         Style::default().fg(Color::Green),
                     ),
+                    InputMode::History => (
+                        "HISTORY: j/k to move, Enter to jump, Esc to close".to_string(),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::Bookmarks => (
+                        "BOOKMARKS: type to filter, Enter to jump, Ctrl-e to edit, Ctrl-d to delete"
+                            .to_string(),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::Jump => (
+                        format!("JUMP: {}", input_buffer),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::Find => (
+                        format!("FIND: {}", input_buffer),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::Grep => (
+                        format!("GREP: {}", input_buffer),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::GoTo => (
+                        format!("GO TO: {}", input_buffer),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::BookmarkEdit => (
+                        format!("EDIT BOOKMARK: {}", input_buffer),
+                        Style::default().fg(Color::LightBlue),
+                    ),
+                    InputMode::ContextMenu => (
+                        "MENU: j/k to move, Enter to run, Esc to close".to_string(),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::Palette => (
+                        format!("PALETTE: {}", input_buffer),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    InputMode::Command => (
+                        format!(":{}", input_buffer),
+                        Style::default().fg(Color::LightGreen),
+                    ),
+                    InputMode::Help => (
+                        format!("HELP: {}", input_buffer),
+                        Style::default().fg(Color::Cyan),
+                    ),
                 };
 
                 let footer = ratatui::widgets::Paragraph::new(footer_text)
                     .style(footer_style)
                     .block(Block::default().borders(Borders::ALL));
-                f.render_widget(footer, nav_column[2]);
-                //open the file for the preview
-                let preview_content = if let Some(entry) = entries.get(selected_file) {
-                    if entry.is_file() {
-                        fs::read_to_string(entry)
-                            .unwrap_or_else(|_| "[Could not read file]".to_string())
+                f.render_widget(footer, nav_column[3]);
+                //the preview is part of the single and the miller layout
+                preview_rect = preview_area;
+                if let Some(preview_area) = preview_area {
+                    //the preview follows the finder and the content search while they are open,
+                    //a line of the content search is shown at its position
+                    let (preview_entry, preview_line) = match (&finder, &grep) {
+                        (Some(finder), _) => (
+                            find_matches
+                                .get(popup_selected)
+                                .map(|path| finder.absolute(path)),
+                            None,
+                        ),
+                        (None, Some(grep)) => match grep_matches.get(popup_selected) {
+                            Some(found) => (Some(grep.absolute(&found.path)), Some(found.line)),
+                            None => (None, None),
+                        },
+                        (None, None) => (entries.get(tab.selected_file).cloned(), None),
+                    };
+                    //the scroll of the wheel starts over for every entry
+                    if preview_entry != preview_scrolled {
+                        preview_scroll = 0;
+                        preview_scrolled = preview_entry.clone();
+                    }
+                    //open the file for the preview
+                    let preview_content = if let Some(entry) = preview_entry {
+                        if entry.is_file() {
+                            fs::read_to_string(entry)
+                                .unwrap_or_else(|_| "[Could not read file]".to_string())
+                        } else {
+                            "".to_string()
+                        }
                     } else {
                         "".to_string()
-                    }
-                } else {
-                    "".to_string()
-                };
+                    };
+
+                    //declaring a "frame" for the preview where it can be rendered
+                    let preview_block = Block::default().title("Preview").borders(Borders::ALL);
+                    let preview = match preview_line {
+                        //not wrapped so the scroll offset is the line number
+                        Some(line) => {
+                            let lines: Vec<Line> = preview_content
+                                .lines()
+                                .enumerate()
+                                .map(|(index, text)| {
+                                    if index + 1 == line {
+                                        Line::styled(
+                                            text.to_string(),
+                                            Style::default()
+                                                .fg(Color::Yellow)
+                                                .add_modifier(Modifier::REVERSED),
+                                        )
+                                    } else {
+                                        Line::raw(text.to_string())
+                                    }
+                                })
+                                .collect();
+                            //the line a third down the preview
+                            let offset = line.saturating_sub(1 + preview_area.height as usize / 3);
+                            Paragraph::new(lines)
+                                .block(preview_block)
+                                .scroll((offset as u16 + preview_scroll, 0))
+                        }
+                        None => Paragraph::new(preview_content)
+                            .block(preview_block)
+                            .wrap(Wrap { trim: true })
+                            .scroll((preview_scroll, 0)),
+                    };
 
-                //declaring a "frame" for the preview where it can be rendered
-                let preview = Paragraph::new(preview_content)
-                    .block(Block::default().title("Preview").borders(Borders::ALL))
-                    .wrap(Wrap { trim: true });
+                    //render the preview
+                    f.render_widget(preview, preview_area);
+                }
 
-                //render the preview
-                f.render_widget(preview, layout[1]);
+                //the results of the finder in place of the file list
+                if let Some(finder) = &finder {
+                    let find_highlight =
+                        highlight::Highlight::new(&input_buffer, FilterMode::Fuzzy, &matcher, None);
+                    let items: Vec<ListItem> = find_matches
+                        .iter()
+                        .map(|path| {
+                            let path = path.to_string_lossy();
+                            let indices = find_highlight.indices(&path);
+                            ListItem::new(Line::from(highlight::spans(
+                                &path,
+                                &indices,
+                                Style::default(),
+                            )))
+                        })
+                        .collect();
+                    let status = if finder.searching {
+                        "searching..."
+                    } else {
+                        "done"
+                    };
+                    popup::render_list_popup(
+                        f,
+                        nav_column[2],
+                        &format!(
+                            "Find in {} ({} files, {})",
+                            finder.root.to_string_lossy(),
+                            finder.found(),
+                            status
+                        ),
+                        items,
+                        popup_selected,
+                    );
+                }
+                //the lines of the content search in place of the file list
+                if let Some(grep) = &grep {
+                    let items: Vec<ListItem> = grep_matches
+                        .iter()
+                        .map(|found| {
+                            let mut spans = vec![Span::styled(
+                                format!("{}:{}: ", found.path.to_string_lossy(), found.line),
+                                Style::default().fg(Color::Magenta),
+                            )];
+                            let indices: Vec<usize> = (found.start..found.end).collect();
+                            spans.extend(highlight::spans(&found.text, &indices, Style::default()));
+                            ListItem::new(Line::from(spans))
+                        })
+                        .collect();
+                    let title = match &grep.error {
+                        Some(error) => format!("Grep: invalid pattern, {}", error),
+                        None => format!(
+                            "Grep {} in {} ({} matches, {}, Ctrl-r for {})",
+                            if grep.regex { "regex" } else { "literal" },
+                            grep.root.to_string_lossy(),
+                            grep_matches.len(),
+                            if grep.searching {
+                                "searching..."
+                            } else {
+                                "done"
+                            },
+                            if grep.regex { "literal" } else { "regex" },
+                        ),
+                    };
+                    popup::render_list_popup(f, nav_column[2], &title, items, popup_selected);
+                }
+                //the history popup over everything else
+                if let InputMode::History = input_mode {
+                    let items: Vec<ListItem> = history
+                        .directories()
+                        .iter()
+                        .map(|directory| ListItem::new(directory.to_string_lossy().to_string()))
+                        .collect();
+                    popup::render_list_popup(
+                        f,
+                        popup::centered_rect(60, 60, f.area()),
+                        "Recent directories",
+                        items,
+                        popup_selected,
+                    );
+                }
+                //the matches of the jump prompt
+                if let InputMode::Jump = input_mode {
+                    let items: Vec<ListItem> = jump_matches
+                        .iter()
+                        .map(|directory| ListItem::new(directory.to_string_lossy().to_string()))
+                        .collect();
+                    popup::render_list_popup(
+                        f,
+                        popup::centered_rect(60, 60, f.area()),
+                        "Jump (frecency)",
+                        items,
+                        popup_selected,
+                    );
+                }
+                //the completions of the path prompts
+                if !completions.is_empty() {
+                    let items: Vec<ListItem> = completions
+                        .iter()
+                        .map(|completion| ListItem::new(completion.clone()))
+                        .collect();
+                    popup::render_list_popup(
+                        f,
+                        popup::centered_rect(60, 40, f.area()),
+                        "Completions (Tab to complete)",
+                        items,
+                        popup_selected,
+                    );
+                }
+                //the keys of the keymap under a header for each group
+                if let InputMode::Help = input_mode {
+                    let mut items: Vec<ListItem> = Vec::new();
+                    let mut current_group = "";
+                    for line in &help_lines {
+                        if line.group != current_group {
+                            items.push(ListItem::new(Span::styled(
+                                line.group,
+                                Style::default()
+                                    .fg(Color::Yellow)
+                                    .add_modifier(Modifier::BOLD),
+                            )));
+                            current_group = line.group;
+                        }
+                        //the name of the action is what the [keys] of the config use
+                        items.push(ListItem::new(Line::from(vec![
+                            Span::styled(
+                                format!("  {:<16}", line.keys),
+                                Style::default().fg(Color::Magenta),
+                            ),
+                            Span::raw(format!("{:<48}", line.description)),
+                            Span::styled(line.name, Style::default().fg(Color::DarkGray)),
+                        ])));
+                    }
+                    popup::render_list_popup(
+                        f,
+                        popup::centered_rect(70, 80, f.area()),
+                        "Keys (type to search)",
+                        items,
+                        popup_selected,
+                    );
+                }
+                //the keys that can follow at the bottom of the file list
+                if !key_hints.is_empty() {
+                    let items: Vec<ListItem> = key_hints
+                        .iter()
+                        .map(|(keys, description)| {
+                            ListItem::new(Line::from(vec![
+                                Span::styled(
+                                    format!("{:<6}", keys),
+                                    Style::default().fg(Color::Magenta),
+                                ),
+                                Span::raw(description.clone()),
+                            ]))
+                        })
+                        .collect();
+                    let height = (key_hints.len() as u16 + 2).min(nav_column[2].height);
+                    let area = Rect {
+                        y: nav_column[2].y + nav_column[2].height - height,
+                        height,
+                        ..nav_column[2]
+                    };
+                    let title = match awaiting_bookmark {
+                        Some(_) => "Bookmarks".to_string(),
+                        None => sequence_label(&pending_keys),
+                    };
+                    popup::render_hints(f, area, &title, items);
+                }
+                //the actions of the command palette with their keys
+                if let InputMode::Palette = input_mode {
+                    let items: Vec<ListItem> = palette_matches
+                        .iter()
+                        .map(|action| {
+                            ListItem::new(Line::from(vec![
+                                Span::raw(format!("{:<22}", action.name().replace('_', " "))),
+                                Span::styled(
+                                    format!("{:<12}", keymap.keys_for(*action)),
+                                    Style::default().fg(Color::Magenta),
+                                ),
+                                Span::styled(
+                                    action.description(),
+                                    Style::default().fg(Color::DarkGray),
+                                ),
+                            ]))
+                        })
+                        .collect();
+                    popup::render_list_popup(
+                        f,
+                        popup::centered_rect(70, 60, f.area()),
+                        &format!("Actions: {}", input_buffer),
+                        items,
+                        popup_selected,
+                    );
+                }
+                //the right click menu at the mouse
+                if let InputMode::ContextMenu = input_mode {
+                    let items: Vec<ListItem> = MENU_ACTIONS
+                        .iter()
+                        .map(|action| {
+                            ListItem::new(Line::from(vec![
+                                Span::raw(format!("{:<18}", action.name().replace('_', " "))),
+                                Span::styled(
+                                    keymap.keys_for(*action),
+                                    Style::default().fg(Color::Magenta),
+                                ),
+                            ]))
+                        })
+                        .collect();
+                    let screen = f.area();
+                    let width = 30.min(screen.width);
+                    let height = (MENU_ACTIONS.len() as u16 + 2).min(screen.height);
+                    menu_area = Rect {
+                        x: menu_position.0.min(screen.width - width),
+                        y: menu_position.1.min(screen.height - height),
+                        width,
+                        height,
+                    };
+                    popup::render_list_popup(f, menu_area, "Actions", items, popup_selected);
+                }
+                //the bookmark popup
+                if let InputMode::Bookmarks | InputMode::BookmarkEdit = input_mode {
+                    let items: Vec<ListItem> = bookmark_matches
+                        .iter()
+                        .map(|(key, path)| {
+                            ListItem::new(format!("{}  {}", key, path.to_string_lossy()))
+                        })
+                        .collect();
+                    popup::render_list_popup(
+                        f,
+                        popup::centered_rect(60, 60, f.area()),
+                        &format!("Bookmarks: {}", bookmark_query),
+                        items,
+                        popup_selected,
+                    );
+                }
             })?;
+            (tab.list_offset, tab.list_height) = list_scroll;
+            (other_pane.list_offset, other_pane.list_height) = other_scroll;
 
             //------------------------------------------------------------------------------
             //
@@ -234,175 +1001,1195 @@ fn main() -> Result<(), io::Error> {
             //------------------------------------------------------------------------------
             //all 100ms
             if event::poll(std::time::Duration::from_millis(100))? {
+                //the action of a key, a click or the context menu, run after the mode handled the event
+                let mut action: Option<Action> = None;
+                let mut given_count: Option<usize> = None;
+                //a command from the command line or bound to a key
+                let mut command_line: Option<String> = None;
                 //when a event is received(key pressed)
-                if let Event::Key(key) = event::read()? {
-                    //swtich on the current mode
-                    match input_mode {
-                        InputMode::Normal => match key.code {
-                            KeyCode::Esc => {
-                                in_search = false;
-                                query.clear();
+                match event::read()? {
+                    Event::Mouse(mouse) => {
+                        let position = Position::new(mouse.column, mouse.row);
+                        match (&input_mode, mouse.kind) {
+                            //a click on an entry of the menu runs it, anywhere else closes the menu
+                            (InputMode::ContextMenu, MouseEventKind::Down(_)) => {
+                                input_mode = InputMode::Normal;
+                                if menu_area.contains(position) && mouse.row > menu_area.y {
+                                    action = MENU_ACTIONS
+                                        .get((mouse.row - menu_area.y - 1) as usize)
+                                        .copied();
+                                }
                             }
-                            KeyCode::Backspace if in_search => {
-                                query.pop();
+                            //a click in the other pane selects there and focuses it
+                            (InputMode::Normal, MouseEventKind::Down(_))
+                                if pane_layout == PaneLayout::Dual
+                                    && other_list_area.contains(position) =>
+                            {
+                                if let Some(row) =
+                                    list_row(other_list_area, mouse.row, detailed_view)
+                                {
+                                    other_pane.selected_file = (other_pane.list_offset + row)
+                                        .min(other_entries.len().saturating_sub(1));
+                                }
+                                std::mem::swap(tab, &mut other_pane);
+                                std::mem::swap(&mut git_cache, &mut other_git_cache);
+                                focus_right = !focus_right;
                             }
-                            KeyCode::Enter if in_search => {
-                                if let Some(entry) = entries.get(selected_file) {
-                                    if entry.is_dir() {
-                                        current_directory = entry.clone();
-                                        selected_file = 0;
-                                    } else if entry.is_file() {
-                                        if file_helper(entry).is_ok() {
-                                            terminal = Some(init_terminal()?);
-                                            current_directory = entry
-                                                .parent()
-                                                .map(PathBuf::from)
-                                                .unwrap_or(current_directory.clone());
-                                            selected_file = 0;
+                            (InputMode::Normal, MouseEventKind::Down(button))
+                                if list_area.contains(position) =>
+                            {
+                                //the path on the bottom border goes to the clicked directory
+                                let breadcrumb = (mouse.row + 1 == list_area.bottom())
+                                    .then(|| {
+                                        file_list::breadcrumb_at(
+                                            &tab.current_directory,
+                                            list_area,
+                                            mouse.column,
+                                        )
+                                    })
+                                    .flatten();
+                                if let Some(directory) = breadcrumb {
+                                    tab.change_directory(directory, &cursor_memory);
+                                } else if let Some(row) =
+                                    list_row(list_area, mouse.row, detailed_view)
+                                    && tab.list_offset + row < entries.len()
+                                {
+                                    let index = tab.list_offset + row;
+                                    tab.selected_file = index;
+                                    match button {
+                                        MouseButton::Left => {
+                                            //the second click on the same entry opens it
+                                            if last_click.is_some_and(|(time, clicked)| {
+                                                clicked == index
+                                                    && time.elapsed() < DOUBLE_CLICK_TIME
+                                            }) {
+                                                action = Some(Action::Open);
+                                                last_click = None;
+                                            } else {
+                                                last_click = Some((Instant::now(), index));
+                                            }
+                                        }
+                                        MouseButton::Right => {
+                                            input_mode = InputMode::ContextMenu;
+                                            menu_position = (mouse.column, mouse.row);
+                                            popup_selected = 0;
                                         }
+                                        MouseButton::Middle => {}
                                     }
                                 }
-                                query.clear();
-                                in_search = false;
-                            }
-                            KeyCode::Char(c) if in_search => {
-                                query.push(c);
-                            }
-                            KeyCode::Char('q') => break,
-                            KeyCode::Char('f') if !in_search => {
-                                in_search = true;
-                                fuzzy_mode = true;
-                            }
-                            KeyCode::Char('s') if !in_search => {
-                                in_search = true;
-                                fuzzy_mode = false;
                             }
-                            KeyCode::Char('r') if !entries.is_empty() => {
-                                input_mode = InputMode::Rename;
-                                input_buffer.clear();
+                            //the wheel scrolls whatever is below the mouse
+                            (
+                                InputMode::Normal,
+                                MouseEventKind::ScrollDown | MouseEventKind::ScrollUp,
+                            ) => {
+                                let down = mouse.kind == MouseEventKind::ScrollDown;
+                                if preview_rect.is_some_and(|area| area.contains(position)) {
+                                    preview_scroll = if down {
+                                        preview_scroll.saturating_add(SCROLL_LINES as u16)
+                                    } else {
+                                        preview_scroll.saturating_sub(SCROLL_LINES as u16)
+                                    };
+                                } else if list_area.contains(position) {
+                                    tab.scroll(SCROLL_LINES, down, entries.len());
+                                } else if other_list_area.contains(position) {
+                                    other_pane.scroll(SCROLL_LINES, down, other_entries.len());
+                                }
                             }
-                            KeyCode::Char('m') if !entries.is_empty() => {
-                                input_mode = InputMode::Move;
-                                input_buffer.clear();
+                            _ => {}
+                        }
+                    }
+                    Event::Key(key) => {
+                        message = None;
+                        //swtich on the current mode
+                        match input_mode {
+                            //typing into the search bar, ctrl keys still reach the keymap
+                            InputMode::Normal
+                                if tab.in_search
+                                    && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                match key.code {
+                                    KeyCode::Esc => {
+                                        tab.in_search = false;
+                                        tab.query.clear();
+                                    }
+                                    KeyCode::Backspace => {
+                                        tab.query.pop();
+                                    }
+                                    KeyCode::Enter => {
+                                        if let Some(entry) = entries.get(tab.selected_file) {
+                                            if entry.is_dir() {
+                                                tab.change_directory(entry.clone(), &cursor_memory);
+                                            } else if entry.is_file()
+                                                && file_helper(entry, None).is_ok()
+                                            {
+                                                terminal = Some(init_terminal(config.mouse)?);
+                                                tab.reveal(entry);
+                                            }
+                                        }
+                                        //the filter can stay for the next directories
+                                        if !config.keep_filter {
+                                            tab.query.clear();
+                                        }
+                                        tab.in_search = false;
+                                    }
+                                    KeyCode::Char(c) => {
+                                        tab.query.push(c);
+                                    }
+                                    _ => {}
+                                }
                             }
-                            KeyCode::Char('d') if !entries.is_empty() => {
-                                input_mode = InputMode::DeleteConfirm;
+                            //the letter after 'm' or '\''
+                            InputMode::Normal if awaiting_bookmark.is_some() => {
+                                if let KeyCode::Char(c) = key.code {
+                                    match awaiting_bookmark {
+                                        //set a bookmark for the current directory
+                                        Some(Action::SetBookmark) => {
+                                            bookmarks.set(c, tab.current_directory.clone());
+                                        }
+                                        //jump to a bookmark
+                                        _ => {
+                                            if let Some(directory) = bookmarks.get(c).cloned() {
+                                                tab.change_directory(directory, &cursor_memory);
+                                            }
+                                        }
+                                    }
+                                }
+                                awaiting_bookmark = None;
                             }
-                            KeyCode::Char('c') if !entries.is_empty() => {
-                                input_mode = InputMode::Create;
+                            InputMode::Normal => {
+                                //collect the keys until they are bound to something
+                                action = match key.code {
+                                    KeyCode::Esc => {
+                                        tab.in_search = false;
+                                        tab.query.clear();
+                                        pending_keys.clear();
+                                        count = None;
+                                        None
+                                    }
+                                    //Alt and a digit goes to that tab like "3gt"
+                                    KeyCode::Char(c @ '1'..='9')
+                                        if key.modifiers.contains(KeyModifiers::ALT)
+                                            && pending_keys.is_empty() =>
+                                    {
+                                        count = Some(c as usize - '0' as usize);
+                                        Some(Action::NextTab)
+                                    }
+                                    //a count like the 10 of "10j", a 0 on its own is no count
+                                    KeyCode::Char(c @ '0'..='9')
+                                        if pending_keys.is_empty()
+                                            && (c != '0' || count.is_some()) =>
+                                    {
+                                        let digit = c as usize - '0' as usize;
+                                        count =
+                                            Some((count.unwrap_or(0) * 10 + digit).min(MAX_COUNT));
+                                        None
+                                    }
+                                    _ => {
+                                        pending_since = Instant::now();
+                                        pending_keys.push(Key::from_event(key));
+                                        match keymap.lookup(&pending_keys) {
+                                            Lookup::Prefix => None,
+                                            Lookup::Command(line) => {
+                                                pending_keys.clear();
+                                                count = None;
+                                                command_line = Some(line);
+                                                None
+                                            }
+                                            Lookup::Actions(actions) => {
+                                                pending_keys.clear();
+                                                //an action that is not available leaves
+                                                //its key to the next thing bound to it
+                                                actions
+                                                    .into_iter()
+                                                    .find(|action| action.is_available(pane_layout))
+                                            }
+                                            Lookup::None => {
+                                                pending_keys.clear();
+                                                count = None;
+                                                None
+                                            }
+                                        }
+                                    }
+                                };
+                                //the count belongs to the action it was typed for
+                                given_count = if action.is_some() { count.take() } else { None };
                             }
-                            KeyCode::Char('j') => {
-                                if !entries.is_empty()
-                                    && selected_file < entries.len().saturating_sub(1)
+                            InputMode::Palette => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                KeyCode::Down => {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char('n')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
                                 {
-                                    selected_file += 1;
+                                    popup_selected += 1;
                                 }
-                            }
-                            KeyCode::Char('k') => {
-                                if selected_file > 0 {
-                                    selected_file -= 1;
+                                KeyCode::Char('p')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected = popup_selected.saturating_sub(1);
                                 }
-                            }
-                            KeyCode::Char('J') => {
-                                selected_file = entries.len().saturating_sub(1);
-                            }
-                            KeyCode::Char('K') => {
-                                selected_file = 0;
-                            }
-                            KeyCode::Char('H') => {
-                                current_directory = root_dir.clone();
-                                selected_file = 0;
-                            }
-                            KeyCode::Char('h') => {
-                                current_directory.pop();
-                                selected_file = 0;
-                            }
-                            KeyCode::Char('l') => {
-                                if let Some(entry) = entries.get(selected_file) {
-                                    if entry.is_dir() {
-                                        current_directory = entry.clone();
-                                        selected_file = 0;
-                                    } else if entry.is_file() {
-                                        if file_helper(entry).is_ok() {
-                                            terminal = Some(init_terminal()?);
-                                            current_directory = entry
-                                                .parent()
-                                                .map(PathBuf::from)
-                                                .unwrap_or(current_directory.clone());
-                                            selected_file = 0;
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                    popup_selected = 0;
+                                }
+                                //run it like its key, file operations take the selection
+                                KeyCode::Enter => {
+                                    action = palette_matches.get(popup_selected).copied();
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                _ => {}
+                            },
+                            InputMode::Command => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                //deleting the ':' leaves the command line like in vim
+                                KeyCode::Backspace if input_buffer.is_empty() => {
+                                    input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                    popup_selected = 0;
+                                }
+                                //complete as far as all completions agree, then take the selected one
+                                KeyCode::Tab => {
+                                    match path_input::common_prefix(&completions) {
+                                        Some(prefix) if prefix.len() > input_buffer.len() => {
+                                            input_buffer = prefix;
+                                        }
+                                        _ => {
+                                            if let Some(completion) =
+                                                completions.get(popup_selected)
+                                            {
+                                                input_buffer = completion.clone();
+                                            }
                                         }
                                     }
+                                    popup_selected = 0;
                                 }
-                            }
-                            _ => {}
-                        },
-                        InputMode::Rename | InputMode::Move => match key.code {
-                            KeyCode::Esc => {
-                                input_mode = InputMode::Normal;
-                                input_buffer.clear();
-                            }
-                            KeyCode::Backspace => {
-                                input_buffer.pop();
-                            }
-                            KeyCode::Char(c) => {
-                                input_buffer.push(c);
-                            }
-                            KeyCode::Enter => {
-                                if let Some(entry) = entries.get(selected_file) {
-                                    match input_mode {
-                                        InputMode::Rename => {
-                                            file_manipulation::rename_file(entry, &input_buffer)
-                                                .ok();
+                                KeyCode::Char('n')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Char('p')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                    popup_selected = 0;
+                                }
+                                //older and newer command lines
+                                KeyCode::Up => {
+                                    let back = history_back.map_or(0, |back| back + 1);
+                                    if let Some(line) = command_history.get(back) {
+                                        input_buffer = line.clone();
+                                        history_back = Some(back);
+                                    }
+                                }
+                                KeyCode::Down => {
+                                    history_back =
+                                        history_back.and_then(|back| back.checked_sub(1));
+                                    input_buffer = history_back
+                                        .and_then(|back| command_history.get(back))
+                                        .cloned()
+                                        .unwrap_or_default();
+                                }
+                                KeyCode::Enter => {
+                                    command_history.push(&input_buffer);
+                                    command_line = Some(std::mem::take(&mut input_buffer));
+                                    input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+                            InputMode::ContextMenu => match key.code {
+                                KeyCode::Esc | KeyCode::Char('q') => {
+                                    input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Down | KeyCode::Char('j') => {
+                                    popup_selected =
+                                        (popup_selected + 1).min(MENU_ACTIONS.len() - 1);
+                                }
+                                KeyCode::Up | KeyCode::Char('k') => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Enter => {
+                                    input_mode = InputMode::Normal;
+                                    action = MENU_ACTIONS.get(popup_selected).copied();
+                                }
+                                _ => {}
+                            },
+                            InputMode::Help => match key.code {
+                                //the first Esc clears the search
+                                KeyCode::Esc if !input_buffer.is_empty() => {
+                                    input_buffer.clear();
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Down => {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::PageDown => {
+                                    popup_selected += 10;
+                                }
+                                KeyCode::PageUp => {
+                                    popup_selected = popup_selected.saturating_sub(10);
+                                }
+                                KeyCode::Char('n')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Char('p')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                    popup_selected = 0;
+                                }
+                                _ => {}
+                            },
+                            InputMode::Rename
+                            | InputMode::Move
+                            | InputMode::Copy
+                            | InputMode::GoTo => {
+                                match key.code {
+                                    KeyCode::Esc => {
+                                        input_mode = InputMode::Normal;
+                                        input_buffer.clear();
+                                    }
+                                    KeyCode::Backspace => {
+                                        input_buffer.pop();
+                                        popup_selected = 0;
+                                    }
+                                    KeyCode::Char(c) => {
+                                        input_buffer.push(c);
+                                        popup_selected = 0;
+                                    }
+                                    KeyCode::Down => {
+                                        popup_selected += 1;
+                                    }
+                                    KeyCode::Up | KeyCode::BackTab => {
+                                        popup_selected = popup_selected.saturating_sub(1);
+                                    }
+                                    //complete as far as all completions agree, then take the selected one
+                                    KeyCode::Tab => {
+                                        match path_input::common_prefix(&completions) {
+                                            Some(prefix) if prefix.len() > input_buffer.len() => {
+                                                input_buffer = prefix;
+                                            }
+                                            _ => {
+                                                if let Some(completion) =
+                                                    completions.get(popup_selected)
+                                                {
+                                                    input_buffer = completion.clone();
+                                                }
+                                            }
                                         }
-                                        InputMode::Move => {
-                                            file_manipulation::move_file(entry, &input_buffer).ok();
+                                        popup_selected = 0;
+                                    }
+                                    KeyCode::Enter => {
+                                        //relative destinations start at the current directory
+                                        let path = path_input::expand(
+                                            &input_buffer,
+                                            &tab.current_directory,
+                                        );
+                                        let destination = path.to_string_lossy();
+                                        let targets = tab.targets(&entries);
+                                        match input_mode {
+                                            //an empty input does nothing
+                                            _ if input_buffer.trim().is_empty() => {}
+                                            //several entries would overwrite each other
+                                            InputMode::Move | InputMode::Copy
+                                                if targets.len() > 1 && !path.is_dir() =>
+                                            {
+                                                message = Some(format!(
+                                                    "{} entries need a directory to go into",
+                                                    targets.len()
+                                                ));
+                                            }
+                                            InputMode::Rename => {
+                                                message =
+                                                    rename_target(tab, &targets, &input_buffer);
+                                            }
+                                            InputMode::Move => {
+                                                message = run_on_targets(
+                                                    tab,
+                                                    &targets,
+                                                    "move",
+                                                    true,
+                                                    |target| {
+                                                        file_manipulation::move_file(
+                                                            target,
+                                                            &destination,
+                                                        )
+                                                    },
+                                                );
+                                            }
+                                            InputMode::Copy => {
+                                                message = run_on_targets(
+                                                    tab,
+                                                    &targets,
+                                                    "copy",
+                                                    false,
+                                                    |target| {
+                                                        file_manipulation::copy_file(
+                                                            target,
+                                                            &destination,
+                                                        )
+                                                    },
+                                                );
+                                            }
+                                            InputMode::GoTo if path.is_dir() => {
+                                                tab.change_directory(path.clone(), &cursor_memory);
+                                            }
+                                            InputMode::GoTo if path.exists() => {
+                                                tab.reveal(&path);
+                                            }
+                                            _ => {}
                                         }
-                                        _ => {}
+                                        input_mode = InputMode::Normal;
+                                        input_buffer.clear();
                                     }
+                                    _ => {}
                                 }
-                                input_mode = InputMode::Normal;
-                                input_buffer.clear();
-                            }
-                            _ => {}
-                        },
-                        InputMode::Create => match key.code {
-                            KeyCode::Esc => {
-                                input_mode = InputMode::Normal;
-                                create_buffer.clear();
-                            }
-                            KeyCode::Backspace => {
-                                create_buffer.pop();
                             }
-                            KeyCode::Char(c) => {
-                                create_buffer.push(c);
+                            InputMode::Create => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    create_buffer.clear();
+                                }
+                                KeyCode::Backspace => {
+                                    create_buffer.pop();
+                                }
+                                KeyCode::Char(c) => {
+                                    create_buffer.push(c);
+                                }
+                                KeyCode::Enter => {
+                                    if let Some(entry) = entries.get(tab.selected_file) {
+                                        println!("{:?}", entry);
+                                        file_manipulation::create_file(
+                                            &tab.current_directory,
+                                            &create_buffer,
+                                        )
+                                        .ok();
+                                    }
+                                    input_mode = InputMode::Normal;
+                                    create_buffer.clear();
+                                }
+                                _ => {}
+                            },
+                            InputMode::Bookmarks => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Down => {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char('n')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Char('p')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char('d')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    if let Some((bookmark, _)) =
+                                        bookmark_matches.get(popup_selected)
+                                    {
+                                        bookmarks.remove(*bookmark);
+                                    }
+                                }
+                                KeyCode::Char('e')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    //edit the key and the path as one line
+                                    if let Some((bookmark, path)) =
+                                        bookmark_matches.get(popup_selected)
+                                    {
+                                        editing_bookmark = Some(*bookmark);
+                                        input_buffer =
+                                            format!("{} {}", bookmark, path.to_string_lossy());
+                                        input_mode = InputMode::BookmarkEdit;
+                                    }
+                                }
+                                KeyCode::Backspace => {
+                                    bookmark_query.pop();
+                                }
+                                KeyCode::Char(c) => {
+                                    bookmark_query.push(c);
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Enter => {
+                                    if let Some((_, directory)) =
+                                        bookmark_matches.get(popup_selected)
+                                    {
+                                        tab.change_directory(directory.clone(), &cursor_memory);
+                                    }
+                                    input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+                            InputMode::BookmarkEdit => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Bookmarks;
+                                    input_buffer.clear();
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                }
+                                KeyCode::Enter => {
+                                    if let (Some(old_key), Some((key, path))) =
+                                        (editing_bookmark, bookmarks::parse_line(&input_buffer))
+                                    {
+                                        bookmarks.remove(old_key);
+                                        bookmarks.set(key, path);
+                                    }
+                                    editing_bookmark = None;
+                                    input_mode = InputMode::Bookmarks;
+                                    input_buffer.clear();
+                                }
+                                _ => {}
+                            },
+                            InputMode::Jump => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                KeyCode::Down => {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Enter => {
+                                    if let Some(directory) = jump_matches.get(popup_selected) {
+                                        tab.change_directory(directory.clone(), &cursor_memory);
+                                    }
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                _ => {}
+                            },
+                            InputMode::Find => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                    finder = None;
+                                }
+                                KeyCode::Down => {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char('n')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Char('p')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Enter => {
+                                    //go to the directory of the file with the file selected
+                                    if let (Some(finder), Some(path)) =
+                                        (&finder, find_matches.get(popup_selected))
+                                    {
+                                        tab.reveal(&finder.absolute(path));
+                                    }
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                    finder = None;
+                                }
+                                _ => {}
+                            },
+                            InputMode::Grep => match key.code {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                    grep = None;
+                                }
+                                KeyCode::Down => {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char('n')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Char('p')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Char('r')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    grep_regex = !grep_regex;
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                    popup_selected = 0;
+                                }
+                                KeyCode::Enter => {
+                                    //open the file at the line, the results stay for the next one
+                                    if let (Some(grep), Some(found)) =
+                                        (&grep, grep_matches.get(popup_selected))
+                                        && file_helper(
+                                            &grep.absolute(&found.path),
+                                            Some(found.line),
+                                        )
+                                        .is_ok()
+                                    {
+                                        terminal = Some(init_terminal(config.mouse)?);
+                                    }
+                                }
+                                _ => {}
+                            },
+                            InputMode::History => match key.code {
+                                KeyCode::Esc | KeyCode::Char('q') => {
+                                    input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Char('j') | KeyCode::Down
+                                    if popup_selected + 1 < history.directories().len() =>
+                                {
+                                    popup_selected += 1;
+                                }
+                                KeyCode::Char('k') | KeyCode::Up => {
+                                    popup_selected = popup_selected.saturating_sub(1);
+                                }
+                                KeyCode::Enter | KeyCode::Char('l') => {
+                                    if let Some(directory) =
+                                        history.directories().get(popup_selected).cloned()
+                                    {
+                                        tab.change_directory(directory, &cursor_memory);
+                                    }
+                                    input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+                            InputMode::SelectPattern | InputMode::DeselectPattern => match key.code
+                            {
+                                KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                KeyCode::Backspace => {
+                                    input_buffer.pop();
+                                }
+                                KeyCode::Char(c) => {
+                                    input_buffer.push(c);
+                                }
+                                KeyCode::Enter => {
+                                    //only the entries of the filtered list are affected
+                                    if !input_buffer.is_empty() {
+                                        message = Some(
+                                            match filter::pattern_entries(
+                                                &entries,
+                                                &input_buffer,
+                                                &matcher,
+                                            ) {
+                                                Ok(matching) => match input_mode {
+                                                    InputMode::SelectPattern => format!(
+                                                        "selected {} more entries",
+                                                        tab.select(&matching)
+                                                    ),
+                                                    _ => format!(
+                                                        "deselected {} entries",
+                                                        tab.deselect(&matching)
+                                                    ),
+                                                },
+                                                Err(error) => format!("invalid pattern, {}", error),
+                                            },
+                                        );
+                                    }
+                                    input_mode = InputMode::Normal;
+                                    input_buffer.clear();
+                                }
+                                _ => {}
+                            },
+                            InputMode::DeleteConfirm => match key.code {
+                                KeyCode::Char('y') => {
+                                    let targets = tab.targets(&entries);
+                                    message = run_on_targets(
+                                        tab,
+                                        &targets,
+                                        "delete",
+                                        true,
+                                        file_manipulation::delete_file,
+                                    );
+                                    input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Char('n') | KeyCode::Esc => {
+                                    input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+                        }
+                    }
+                    _ => {}
+                }
+                //run the command line or a command bound to a key
+                if let Some(line) = command_line {
+                    match command::parse(&line) {
+                        Ok(Command::Action(found)) => {
+                            action = Some(found);
+                        }
+                        Ok(Command::Cd(path)) => {
+                            //the home directory without a path like in a shell
+                            let path = if path.is_empty() { "~" } else { &path };
+                            let path = path_input::expand(path, &tab.current_directory);
+                            if path.is_dir() {
+                                tab.change_directory(path, &cursor_memory);
+                            } else if path.exists() {
+                                tab.reveal(&path);
+                            } else {
+                                message =
+                                    Some(format!("no such directory {}", path.to_string_lossy()));
                             }
-                            KeyCode::Enter => {
-                                if let Some(entry) = entries.get(selected_file) {
-                                    println!("{:?}", entry);
-                                    file_manipulation::create_file(
-                                        &current_directory,
-                                        &create_buffer,
-                                    )
-                                    .ok();
+                        }
+                        Ok(Command::Mkdir(name)) => {
+                            match file_manipulation::create_directory(&tab.current_directory, &name)
+                            {
+                                Ok(()) => {
+                                    tab.select_name =
+                                        Path::new(&name).iter().next().map(OsString::from)
+                                }
+                                Err(error) => {
+                                    message = Some(format!("could not create {}: {}", name, error))
                                 }
-                                input_mode = InputMode::Normal;
-                                create_buffer.clear();
                             }
-                            _ => {}
-                        },
-                        InputMode::DeleteConfirm => match key.code {
-                            KeyCode::Char('y') => {
-                                if let Some(entry) = entries.get(selected_file) {
-                                    file_manipulation::delete_file(entry).ok();
+                        }
+                        Ok(Command::Touch(name)) => {
+                            match file_manipulation::create_file(&tab.current_directory, &name) {
+                                Ok(()) => tab.select_name = Some(OsString::from(&name)),
+                                Err(error) => {
+                                    message = Some(format!("could not create {}: {}", name, error))
                                 }
-                                input_mode = InputMode::Normal;
                             }
-                            KeyCode::Char('n') | KeyCode::Esc => {
-                                input_mode = InputMode::Normal;
+                        }
+                        Ok(Command::Rename(new_name)) => {
+                            let targets = tab.targets(&entries);
+                            message = rename_target(tab, &targets, &new_name);
+                        }
+                        Ok(Command::Copy(destination) | Command::Move(destination))
+                            if tab.targets(&entries).is_empty() =>
+                        {
+                            message = Some(format!("nothing to put into {}", destination));
+                        }
+                        Ok(Command::Copy(destination) | Command::Move(destination))
+                            if tab.selection.len() > 1
+                                && !path_input::expand(&destination, &tab.current_directory)
+                                    .is_dir() =>
+                        {
+                            message = Some(format!(
+                                "{} entries need a directory to go into",
+                                tab.selection.len()
+                            ));
+                        }
+                        Ok(Command::Copy(destination)) => {
+                            //relative destinations start at the current directory
+                            let destination =
+                                path_input::expand(&destination, &tab.current_directory);
+                            let targets = tab.targets(&entries);
+                            message = run_on_targets(tab, &targets, "copy", false, |target| {
+                                file_manipulation::copy_file(target, &destination.to_string_lossy())
+                            });
+                        }
+                        Ok(Command::Move(destination)) => {
+                            let destination =
+                                path_input::expand(&destination, &tab.current_directory);
+                            let targets = tab.targets(&entries);
+                            message = run_on_targets(tab, &targets, "move", true, |target| {
+                                file_manipulation::move_file(target, &destination.to_string_lossy())
+                            });
+                        }
+                        Ok(Command::Select(pattern)) => {
+                            message = Some(
+                                match filter::pattern_entries(&entries, &pattern, &matcher) {
+                                    Ok(matching) => {
+                                        format!("selected {} more entries", tab.select(&matching))
+                                    }
+                                    Err(error) => format!("invalid pattern, {}", error),
+                                },
+                            );
+                        }
+                        Ok(Command::Deselect(pattern)) => {
+                            message = Some(
+                                match filter::pattern_entries(&entries, &pattern, &matcher) {
+                                    Ok(matching) => {
+                                        format!("deselected {} entries", tab.deselect(&matching))
+                                    }
+                                    Err(error) => format!("invalid pattern, {}", error),
+                                },
+                            );
+                        }
+                        Ok(Command::Sort(mode, reverse)) => {
+                            tab.sort_mode = mode;
+                            tab.sort_reverse = reverse;
+                        }
+                        Ok(Command::Set(flag, value)) => {
+                            let option = match flag {
+                                Flag::Hidden => &mut show_hidden,
+                                Flag::Details => &mut detailed_view,
+                                Flag::Reverse => &mut tab.sort_reverse,
+                            };
+                            //without a value the option switches
+                            *option = value.unwrap_or(!*option);
+                        }
+                        Ok(Command::Layout(layout)) => {
+                            pane_layout = layout;
+                        }
+                        Err(error) => {
+                            message = Some(error);
+                        }
+                    }
+                }
+                let times = given_count.unwrap_or(1);
+                match action {
+                    None => {}
+                    Some(Action::Quit) => break,
+                    Some(Action::Help) => {
+                        input_mode = InputMode::Help;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                    }
+                    Some(Action::CommandPalette) => {
+                        input_mode = InputMode::Palette;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                    }
+                    Some(Action::CommandLine) => {
+                        input_mode = InputMode::Command;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                        history_back = None;
+                    }
+                    Some(Action::FuzzySearch) => {
+                        tab.in_search = true;
+                        tab.filter_mode = FilterMode::Fuzzy;
+                    }
+                    Some(Action::Search) => {
+                        tab.in_search = true;
+                        tab.filter_mode = FilterMode::Substring;
+                    }
+                    Some(Action::RegexSearch) => {
+                        tab.in_search = true;
+                        tab.filter_mode = FilterMode::Regex;
+                    }
+                    Some(Action::GlobSearch) => {
+                        tab.in_search = true;
+                        tab.filter_mode = FilterMode::Glob;
+                    }
+                    //many entries cannot get the same name
+                    Some(Action::Rename) if tab.selection.len() > 1 => {
+                        message = Some(format!(
+                            "rename works on one entry, {} are selected",
+                            tab.selection.len()
+                        ));
+                    }
+                    Some(Action::Rename) if !tab.targets(&entries).is_empty() => {
+                        input_mode = InputMode::Rename;
+                        input_buffer.clear();
+                    }
+                    Some(Action::Move) if !tab.targets(&entries).is_empty() => {
+                        input_mode = InputMode::Move;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                        //the other pane is the default destination
+                        if pane_layout == PaneLayout::Dual {
+                            input_buffer.push_str(&other_pane.current_directory.to_string_lossy());
+                        }
+                    }
+                    Some(Action::Copy) if !tab.targets(&entries).is_empty() => {
+                        input_mode = InputMode::Copy;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                        if pane_layout == PaneLayout::Dual {
+                            input_buffer.push_str(&other_pane.current_directory.to_string_lossy());
+                        }
+                    }
+                    Some(Action::Delete) if !tab.targets(&entries).is_empty() => {
+                        input_mode = InputMode::DeleteConfirm;
+                    }
+                    Some(Action::Create) if !entries.is_empty() => {
+                        input_mode = InputMode::Create;
+                    }
+                    Some(Action::ToggleHidden) => {
+                        show_hidden = !show_hidden;
+                    }
+                    Some(Action::ToggleDetails) => {
+                        detailed_view = !detailed_view;
+                    }
+                    Some(Action::CycleSort) => {
+                        tab.sort_mode = tab.sort_mode.next();
+                    }
+                    Some(Action::ReverseSort) => {
+                        tab.sort_reverse = !tab.sort_reverse;
+                    }
+                    Some(action @ (Action::SetBookmark | Action::JumpToBookmark)) => {
+                        awaiting_bookmark = Some(action);
+                        pending_since = Instant::now();
+                    }
+                    //"3gt" goes to the third tab
+                    Some(Action::NextTab) => match given_count {
+                        Some(number) if number <= tab_titles.len() => {
+                            active_tab = number - 1;
+                        }
+                        Some(_) => {}
+                        None => {
+                            active_tab = (active_tab + 1) % tab_titles.len();
+                        }
+                    },
+                    Some(Action::PreviousTab) => {
+                        let back = times % tab_titles.len();
+                        active_tab = (active_tab + tab_titles.len() - back) % tab_titles.len();
+                    }
+                    //type the path of a directory to go to
+                    Some(Action::GoToPath) => {
+                        input_mode = InputMode::GoTo;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                    }
+                    Some(Action::Jump) => {
+                        input_mode = InputMode::Jump;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                    }
+                    Some(Action::Find) => {
+                        //find files in the whole tree below the current directory
+                        finder = Some(finder::Finder::start(
+                            tab.current_directory.clone(),
+                            show_hidden,
+                            &config,
+                            &ignore_globs,
+                        ));
+                        input_mode = InputMode::Find;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                    }
+                    Some(Action::Grep) => {
+                        //search the contents of the files below the current directory
+                        input_mode = InputMode::Grep;
+                        input_buffer.clear();
+                        popup_selected = 0;
+                        grep = None;
+                    }
+                    Some(Action::ToggleSelection) => {
+                        //select the entry and go to the next one
+                        if let Some(entry) = entries.get(tab.selected_file) {
+                            tab.toggle_selection(entry);
+                            if tab.selected_file + 1 < entries.len() {
+                                tab.selected_file += 1;
                             }
-                            _ => {}
-                        },
+                        }
+                    }
+                    Some(Action::SelectPattern) => {
+                        input_mode = InputMode::SelectPattern;
+                        input_buffer.clear();
+                    }
+                    Some(Action::DeselectPattern) => {
+                        input_mode = InputMode::DeselectPattern;
+                        input_buffer.clear();
+                    }
+                    Some(Action::InvertSelection) => {
+                        let count = tab.invert_selection(&entries);
+                        message = Some(format!(
+                            "inverted the selection, {} of {} entries selected",
+                            count,
+                            entries.len()
+                        ));
+                    }
+                    Some(Action::SelectAll) => {
+                        let count = tab.select(&entries);
+                        message = Some(format!("selected {} more entries", count));
+                    }
+                    Some(Action::Bookmarks) => {
+                        input_mode = InputMode::Bookmarks;
+                        bookmark_query.clear();
+                        popup_selected = 0;
+                    }
+                    Some(Action::CycleLayout) => {
+                        pane_layout = pane_layout.next();
+                    }
+                    Some(Action::SwitchPane) => {
+                        //switch the focus to the other pane
+                        std::mem::swap(tab, &mut other_pane);
+                        std::mem::swap(&mut git_cache, &mut other_git_cache);
+                        focus_right = !focus_right;
+                    }
+                    Some(Action::Back) => {
+                        tab.go_back(&cursor_memory);
+                    }
+                    Some(Action::Forward) => {
+                        tab.go_forward(&cursor_memory);
+                    }
+                    Some(Action::PreviousDirectory) => {
+                        if let Some(previous) = tab.previous_directory() {
+                            tab.change_directory(previous, &cursor_memory);
+                        }
                     }
+                    Some(Action::History) => {
+                        input_mode = InputMode::History;
+                        //the first entry is the current directory, start at the one before
+                        popup_selected = 1.min(history.directories().len().saturating_sub(1));
+                    }
+                    Some(Action::NewTab) => {
+                        //open a new tab in the current directory right after this one
+                        let directory = tab.current_directory.clone();
+                        tabs.insert(active_tab + 1, Tab::new(directory, &config));
+                        active_tab += 1;
+                    }
+                    Some(Action::CloseTab) if tab_titles.len() > 1 => {
+                        tabs.remove(active_tab);
+                        active_tab = active_tab.min(tabs.len() - 1);
+                    }
+                    Some(Action::MoveDown) => {
+                        tab.selected_file = tab
+                            .selected_file
+                            .saturating_add(times)
+                            .min(entries.len().saturating_sub(1));
+                    }
+                    Some(Action::MoveUp) => {
+                        tab.selected_file = tab.selected_file.saturating_sub(times);
+                    }
+                    //with a count both go to that entry like "10G" in vim
+                    Some(Action::Bottom | Action::Top) if given_count.is_some() => {
+                        tab.selected_file = (times - 1).min(entries.len().saturating_sub(1));
+                    }
+                    Some(Action::Bottom) => {
+                        tab.selected_file = entries.len().saturating_sub(1);
+                    }
+                    Some(Action::Top) => {
+                        tab.selected_file = 0;
+                    }
+                    Some(Action::HalfPageDown) => {
+                        tab.scroll(
+                            times.saturating_mul((tab.list_height / 2).max(1)),
+                            true,
+                            entries.len(),
+                        );
+                    }
+                    Some(Action::HalfPageUp) => {
+                        tab.scroll(
+                            times.saturating_mul((tab.list_height / 2).max(1)),
+                            false,
+                            entries.len(),
+                        );
+                    }
+                    Some(Action::PageDown) => {
+                        tab.scroll(
+                            times.saturating_mul(tab.list_height.max(1)),
+                            true,
+                            entries.len(),
+                        );
+                    }
+                    Some(Action::PageUp) => {
+                        tab.scroll(
+                            times.saturating_mul(tab.list_height.max(1)),
+                            false,
+                            entries.len(),
+                        );
+                    }
+                    //a count counts from the top or the bottom of the screen
+                    Some(Action::ScreenTop) => {
+                        tab.select_on_screen(times - 1, entries.len());
+                    }
+                    Some(Action::ScreenMiddle) => {
+                        //the middle of the entries when they do not fill the screen
+                        let shown = tab
+                            .list_height
+                            .min(entries.len().saturating_sub(tab.list_offset));
+                        tab.select_on_screen(shown.saturating_sub(1) / 2, entries.len());
+                    }
+                    Some(Action::ScreenBottom) => {
+                        let shown = tab
+                            .list_height
+                            .min(entries.len().saturating_sub(tab.list_offset));
+                        tab.select_on_screen(shown.saturating_sub(times), entries.len());
+                    }
+                    Some(Action::Home) => {
+                        tab.change_directory(root_dir.clone(), &cursor_memory);
+                    }
+                    Some(Action::GoUp) => {
+                        tab.go_up();
+                    }
+                    Some(Action::Open) => {
+                        if let Some(entry) = entries.get(tab.selected_file) {
+                            if entry.is_dir() {
+                                tab.change_directory(entry.clone(), &cursor_memory);
+                            } else if entry.is_file() && file_helper(entry, None).is_ok() {
+                                terminal = Some(init_terminal(config.mouse)?);
+                                tab.reveal(entry);
+                            }
+                        }
+                    }
+                    Some(_) => {}
                 }
             }
         }
@@ -416,26 +2203,196 @@ fn main() -> Result<(), io::Error> {
     //
     // -------------------------------------------------------------------------------
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
     std::process::exit(0);
 }
 
+//the query of the tab for highlighting the matched characters, None without a search
+fn search_highlight<'a>(
+    tab: &'a Tab,
+    matcher: &'a SkimMatcherV2,
+) -> Option<highlight::Highlight<'a>> {
+    //only the name pattern of a query like "size>1M report" matched the names
+    let name = query::Query::parse(&tab.query).ok()?.name;
+    //the regex is the one the filter compiled for listing the entries
+    let regex = tab.filter_cache.regex(&name);
+    (!name.is_empty()).then(|| highlight::Highlight::new(&name, tab.filter_mode, matcher, regex))
+}
+
+//get the entries of a tab, sorted and filtered by its query
+//returns the entries, how many entries got hidden and why the query is invalid
+fn tab_entries(
+    tab: &mut Tab,
+    show_hidden: bool,
+    config: &Config,
+    ignore_globs: &Arc<GlobSet>,
+    matcher: &SkimMatcherV2,
+) -> (Vec<PathBuf>, usize, Option<String>) {
+    //get the entries from the current directory but unfiltered by the query
+    let (mut entries_raw, hidden_count) =
+        get_entries(&tab.current_directory, show_hidden, config, ignore_globs);
+    sort::sort_entries(
+        &mut entries_raw,
+        tab.sort_mode,
+        tab.sort_reverse,
+        config.dirs_first,
+    );
+
+    //split the query into its predicates and the name pattern
+    let query = match query::Query::parse(&tab.query) {
+        Ok(query) => query,
+        Err(error) => return (entries_raw, hidden_count, Some(error)),
+    };
+    let candidates: Vec<PathBuf> = match query.depth {
+        //a recursive query looks at the whole tree below the directory
+        Some(depth) => {
+            let mut candidates: Vec<PathBuf> = tab
+                .tree_cache
+                .get(
+                    &tab.current_directory,
+                    depth,
+                    show_hidden,
+                    config,
+                    ignore_globs,
+                )
+                .iter()
+                .filter(|(entry, metadata)| query.matches(entry, metadata.as_ref()))
+                .map(|(entry, _)| entry.clone())
+                .collect();
+            sort::sort_entries(
+                &mut candidates,
+                tab.sort_mode,
+                tab.sort_reverse,
+                config.dirs_first,
+            );
+            candidates
+        }
+        None if query.is_structured() => entries_raw
+            .iter()
+            .filter(|entry| query.matches(entry, fs::metadata(entry).ok().as_ref()))
+            .cloned()
+            .collect(),
+        None => entries_raw.clone(),
+    };
+
+    //filter the entries based on the name pattern, an invalid pattern shows everything
+    match filter::filter_entries(
+        candidates,
+        &query.name,
+        tab.filter_mode,
+        matcher,
+        &mut tab.filter_cache,
+    ) {
+        Ok(entries) => (entries, hidden_count, None),
+        Err(error) => (entries_raw, hidden_count, Some(error)),
+    }
+}
+
 //get the entries from the directory
-fn get_entries(path: &PathBuf) -> Vec<PathBuf> {
-    fs::read_dir(path)
-        .unwrap_or_else(|_| fs::read_dir(".").unwrap())
+//returns the visible entries and how many entries got hidden
+fn get_entries(
+    path: &PathBuf,
+    show_hidden: bool,
+    config: &Config,
+    ignore_globs: &Arc<GlobSet>,
+) -> (Vec<PathBuf>, usize) {
+    let path = if fs::read_dir(path).is_ok() {
+        path.clone()
+    } else {
+        PathBuf::from(".")
+    };
+    let all: Vec<PathBuf> = fs::read_dir(&path)
+        .map(|read_dir| {
+            read_dir
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    if show_hidden {
+        return (all, 0);
+    }
+
+    //walk only the direct children, the walker handles dotfiles and the ignore files
+    let globs = Arc::clone(ignore_globs);
+    let respect = config.respect_ignore_files;
+    let visible: Vec<PathBuf> = WalkBuilder::new(&path)
+        .max_depth(Some(1))
+        .hidden(true)
+        .parents(respect)
+        .ignore(respect)
+        .git_ignore(respect)
+        .git_global(respect)
+        .git_exclude(respect)
+        .filter_entry(move |entry| entry.depth() == 0 || !globs.is_match(entry.file_name()))
+        .build()
         .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .collect()
+        .filter(|entry| entry.depth() == 1)
+        .map(|entry| entry.into_path())
+        .collect();
+    let hidden_count = all.len().saturating_sub(visible.len());
+    (visible, hidden_count)
+}
+
+//run a file operation on every target, the done ones leave the selection if they are gone now,
+//returns what went wrong
+fn run_on_targets(
+    tab: &mut Tab,
+    targets: &[PathBuf],
+    verb: &str,
+    gone: bool,
+    operation: impl Fn(&Path) -> io::Result<()>,
+) -> Option<String> {
+    let mut failed = 0;
+    let mut first_error = None;
+    for target in targets {
+        match operation(target) {
+            Ok(()) if gone => {
+                tab.selection.remove(target);
+            }
+            Ok(()) => {}
+            Err(error) => {
+                failed += 1;
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    first_error.map(|error| match failed {
+        1 => format!("could not {}: {}", verb, error),
+        _ => format!("could not {} {} entries: {}", verb, failed, error),
+    })
 }
 
-//open the file in nvim
+//rename the only target and put the cursor on its new name, returns what went wrong
+fn rename_target(tab: &mut Tab, targets: &[PathBuf], new_name: &str) -> Option<String> {
+    match targets {
+        [target] => match file_manipulation::rename_file(target, new_name) {
+            Ok(()) => {
+                tab.selection.remove(target);
+                tab.select_name = Some(OsString::from(new_name));
+                None
+            }
+            Err(error) => Some(format!("could not rename: {}", error)),
+        },
+        [] => None,
+        _ => Some(format!(
+            "rename works on one entry, {} are selected",
+            targets.len()
+        )),
+    }
+}
+
+//open the file in nvim, at the line if there is one
 #[allow(unused)]
-fn file_helper(path: &PathBuf) -> io::Result<()> {
+fn file_helper(path: &PathBuf, line: Option<usize>) -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
 
-    Command::new("nvim")
+    let mut command = process::Command::new("nvim");
+    if let Some(line) = line {
+        command.arg(format!("+{}", line));
+    }
+    command
         .arg(path)
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
@@ -444,13 +2401,21 @@ fn file_helper(path: &PathBuf) -> io::Result<()> {
     Ok(())
 }
 
+//the index on the screen of the entry in a row of a file list, None for the borders
+fn list_row(area: Rect, row: u16, detailed_view: bool) -> Option<usize> {
+    //the detailed view has a header row below the border
+    let first = area.y + 1 + u16::from(detailed_view);
+    (row >= first && row + 1 < area.bottom()).then(|| (row - first) as usize)
+}
+
 //initialize the terminal
-fn init_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
+fn init_terminal(mouse: bool) -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend)
 }
-//
-//TODO:add a hotkey helper,
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+// turn what was typed into a path, "~" is the home directory and
+// relative paths start at the given directory
+pub fn expand(input: &str, base: &Path) -> PathBuf {
+    let input = input.trim();
+    let path = if input == "~" {
+        dirs::home_dir().unwrap_or_default()
+    } else if let Some(rest) = input.strip_prefix("~/") {
+        dirs::home_dir().unwrap_or_default().join(rest)
+    } else {
+        PathBuf::from(input)
+    };
+    let path = if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    };
+    //resolve "." and ".." without following symlinks like "cd" does
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+// the inputs the typed path can be completed to, directories end with a '/'
+pub fn completions(input: &str, base: &Path, dirs_only: bool) -> Vec<String> {
+    //"~" alone is completed to the home directory itself
+    if input == "~" {
+        return vec!["~/".to_string()];
+    }
+    //everything up to the last '/' stays as typed, the rest is the prefix of the name
+    let (typed_dir, prefix) = match input.rfind('/') {
+        Some(index) => input.split_at(index + 1),
+        None => ("", input),
+    };
+    let Ok(read_dir) = fs::read_dir(expand(typed_dir, base)) else {
+        return Vec::new();
+    };
+    let mut completions: Vec<String> = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            //dotfiles only when the prefix asks for them
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let is_dir = entry.path().is_dir();
+            if is_dir {
+                Some(format!("{}{}/", typed_dir, name))
+            } else if !dirs_only {
+                Some(format!("{}{}", typed_dir, name))
+            } else {
+                None
+            }
+        })
+        .collect();
+    completions.sort();
+    completions
+}
+
+// the longest start all completions share, what Tab can insert without choosing
+pub fn common_prefix(completions: &[String]) -> Option<String> {
+    let first = completions.first()?;
+    let mut length = first.len();
+    for completion in &completions[1..] {
+        length = first
+            .char_indices()
+            .zip(completion.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(length);
+    }
+    Some(first[..length].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn common_prefix_is_what_all_completions_share() {
+        assert_eq!(common_prefix(&[]), None);
+        assert_eq!(
+            common_prefix(&strings(&["src/main.rs"])),
+            Some("src/main.rs".to_string())
+        );
+        assert_eq!(
+            common_prefix(&strings(&["src/main.rs", "src/mod.rs", "src/music/"])),
+            Some("src/m".to_string())
+        );
+        assert_eq!(
+            common_prefix(&strings(&["docs/", "src/"])),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn common_prefix_keeps_whole_characters() {
+        assert_eq!(
+            common_prefix(&strings(&["fähre", "fäden", "fä"])),
+            Some("fä".to_string())
+        );
+        assert_eq!(common_prefix(&strings(&["ä", "ö"])), Some(String::new()));
+    }
+}
@@ -0,0 +1,320 @@
+use std::cmp::Ordering;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use globset::GlobSet;
+
+use crate::config::Config;
+
+// how long a walked tree is reused before it is walked again
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+// a recursive query stops walking after this many entries
+const MAX_TREE_ENTRIES: usize = 20000;
+
+// one condition of the query like "size>100M"
+enum Predicate {
+    //compared with the size in bytes
+    Size(Ordering, u64),
+    //compared with the time since the last modification
+    Modified(Ordering, Duration),
+    //'f' file, 'd' directory or 'l' symlink
+    Type(char),
+    //the extension without the dot, case insensitive
+    Extension(String),
+}
+
+// the search bar split into predicates and the name pattern,
+// e.g. "size>100M mtime<7d type:f ext:log report"
+pub struct Query {
+    //the words that are no predicate, filtered with the mode of the search
+    pub name: String,
+    predicates: Vec<Predicate>,
+    //"depth:N" looks N levels deep instead of only at the listing
+    pub depth: Option<usize>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut name = Vec::new();
+        let mut predicates = Vec::new();
+        let mut depth = None;
+        for word in input.split_whitespace() {
+            //a word like "sizes" without a comparison is part of the name
+            if let Some((ordering, value)) = word.strip_prefix("size").and_then(comparison) {
+                let size = parse_size(value).ok_or(format!("invalid size '{}'", value))?;
+                predicates.push(Predicate::Size(ordering, size));
+            } else if let Some((ordering, value)) = word.strip_prefix("mtime").and_then(comparison)
+            {
+                let age = parse_duration(value).ok_or(format!("invalid age '{}'", value))?;
+                predicates.push(Predicate::Modified(ordering, age));
+            } else if let Some(kind) = word.strip_prefix("type:") {
+                match kind {
+                    "f" | "d" | "l" => {
+                        predicates.push(Predicate::Type(kind.chars().next().unwrap_or('f')))
+                    }
+                    _ => return Err(format!("invalid type '{}', use f, d or l", kind)),
+                }
+            } else if let Some(extension) = word.strip_prefix("ext:") {
+                predicates.push(Predicate::Extension(
+                    extension.trim_start_matches('.').to_lowercase(),
+                ));
+            } else if let Some(value) = word.strip_prefix("depth:") {
+                depth = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid depth '{}'", value))?,
+                );
+            } else {
+                name.push(word);
+            }
+        }
+        Ok(Query {
+            name: name.join(" "),
+            predicates,
+            depth,
+        })
+    }
+
+    //if there is more than a name pattern
+    pub fn is_structured(&self) -> bool {
+        !self.predicates.is_empty() || self.depth.is_some()
+    }
+
+    //if the entry fulfills every predicate, the metadata of a symlink is the one of its target
+    pub fn matches(&self, entry: &Path, metadata: Option<&Metadata>) -> bool {
+        self.predicates.iter().all(|predicate| match predicate {
+            Predicate::Size(ordering, size) => metadata.is_some_and(|metadata| {
+                metadata.is_file() && metadata.len().cmp(size) == *ordering
+            }),
+            Predicate::Modified(ordering, age) => metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|modified_ago| modified_ago.cmp(age) == *ordering),
+            Predicate::Type('l') => entry.is_symlink(),
+            Predicate::Type('d') => metadata.is_some_and(Metadata::is_dir),
+            Predicate::Type(_) => metadata.is_some_and(Metadata::is_file),
+            Predicate::Extension(extension) => entry
+                .extension()
+                .is_some_and(|found| found.to_string_lossy().to_lowercase() == *extension),
+        })
+    }
+}
+
+// the ordering of ">", "<" or "=" at the start of what follows the predicate name
+fn comparison(rest: &str) -> Option<(Ordering, &str)> {
+    let mut chars = rest.chars();
+    let ordering = match chars.next()? {
+        '>' => Ordering::Greater,
+        '<' => Ordering::Less,
+        '=' => Ordering::Equal,
+        _ => return None,
+    };
+    Some((ordering, chars.as_str()))
+}
+
+// "100M", "1.5G" or "512" bytes, the units are powers of 1024
+fn parse_size(value: &str) -> Option<u64> {
+    let value = value.to_uppercase();
+    let value = value.trim_end_matches('B');
+    let (number, unit) = match value.char_indices().last()? {
+        (index, c) if c.is_ascii_alphabetic() => (&value[..index], c),
+        _ => (value, ' '),
+    };
+    let factor: u64 = match unit {
+        ' ' => 1,
+        'K' => 1 << 10,
+        'M' => 1 << 20,
+        'G' => 1 << 30,
+        'T' => 1 << 40,
+        _ => return None,
+    };
+    let number: f64 = number.parse().ok()?;
+    Some((number * factor as f64) as u64)
+}
+
+// "30s", "10m", "12h", "7d" or "2w"
+fn parse_duration(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let number: u64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    let seconds = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    //a huge number is invalid like any other value instead of overflowing
+    Some(Duration::from_secs(number.checked_mul(seconds)?))
+}
+
+// the walked entries with their metadata, None if it could not be read
+type TreeEntries = Vec<(PathBuf, Option<Metadata>)>;
+
+// the entries below a directory for recursive queries, walked on a worker thread
+// and again every few seconds, every tab keeps its own
+pub struct TreeCache {
+    //the directory, depth and hidden setting the entries belong to
+    key: Option<(PathBuf, usize, bool)>,
+    loaded_at: Instant,
+    entries: TreeEntries,
+    //the walk that is running, it sends all the entries at once when it is through
+    receiver: Option<Receiver<TreeEntries>>,
+    //set when the walk is replaced or the cache dropped so the worker stops
+    cancel: Arc<AtomicBool>,
+}
+
+impl TreeCache {
+    pub fn new() -> Self {
+        TreeCache {
+            key: None,
+            loaded_at: Instant::now(),
+            entries: Vec::new(),
+            receiver: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    //the entries of the last walk, empty until the first walk of a new directory is through
+    pub fn get(
+        &mut self,
+        dir: &Path,
+        depth: usize,
+        show_hidden: bool,
+        config: &Config,
+        ignore_globs: &Arc<GlobSet>,
+    ) -> &[(PathBuf, Option<Metadata>)] {
+        let key = (dir.to_path_buf(), depth, show_hidden);
+        if self.key.as_ref() != Some(&key) {
+            //the entries of another directory would be wrong, so they are dropped
+            self.entries.clear();
+            self.walk(&key, config, ignore_globs);
+            self.key = Some(key);
+        } else if self.receiver.is_none() && self.loaded_at.elapsed() >= REFRESH_INTERVAL {
+            //the old entries stay until the new walk is through
+            self.walk(&key, config, ignore_globs);
+        }
+        if let Some(receiver) = &self.receiver {
+            match receiver.try_recv() {
+                Ok(entries) => {
+                    self.entries = entries;
+                    self.receiver = None;
+                    self.loaded_at = Instant::now();
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.receiver = None,
+            }
+        }
+        &self.entries
+    }
+
+    fn walk(&mut self, key: &(PathBuf, usize, bool), config: &Config, ignore_globs: &Arc<GlobSet>) {
+        let (dir, depth, show_hidden) = key;
+        self.cancel.store(true, AtomicOrdering::Relaxed);
+        self.cancel = Arc::new(AtomicBool::new(false));
+        let walker = config
+            .tree_walker(dir, *show_hidden, ignore_globs)
+            .max_depth(Some(*depth))
+            .build();
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::clone(&self.cancel);
+        thread::spawn(move || {
+            let mut entries = Vec::new();
+            for entry in walker.filter_map(Result::ok) {
+                if cancel.load(AtomicOrdering::Relaxed) {
+                    return;
+                }
+                if entries.len() >= MAX_TREE_ENTRIES {
+                    break;
+                }
+                if entry.depth() > 0 {
+                    let metadata = fs::metadata(entry.path()).ok();
+                    entries.push((entry.into_path(), metadata));
+                }
+            }
+            sender.send(entries).ok();
+        });
+        self.receiver = Some(receiver);
+    }
+}
+
+impl Drop for TreeCache {
+    fn drop(&mut self) {
+        self.cancel.store(true, AtomicOrdering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predicates_are_split_from_the_name() {
+        let query = Query::parse("size>100M report mtime<7d type:f ext:.LOG final").unwrap();
+        assert_eq!(query.name, "report final");
+        assert!(query.is_structured());
+        assert_eq!(query.depth, None);
+        assert!(matches!(
+            query.predicates[..],
+            [
+                Predicate::Size(Ordering::Greater, 104857600),
+                Predicate::Modified(Ordering::Less, _),
+                Predicate::Type('f'),
+                Predicate::Extension(_),
+            ]
+        ));
+        assert!(
+            matches!(&query.predicates[3], Predicate::Extension(extension) if extension == "log")
+        );
+    }
+
+    #[test]
+    fn a_plain_name_is_not_structured() {
+        let query = Query::parse("sizes of things").unwrap();
+        assert_eq!(query.name, "sizes of things");
+        assert!(!query.is_structured());
+        assert_eq!(Query::parse("depth:3 rs").unwrap().depth, Some(3));
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        assert_eq!(
+            Query::parse("size>lots").err(),
+            Some("invalid size 'lots'".to_string())
+        );
+        assert_eq!(
+            Query::parse("mtime<7y").err(),
+            Some("invalid age '7y'".to_string())
+        );
+        assert_eq!(
+            Query::parse("mtime<100000000000000000w").err(),
+            Some("invalid age '100000000000000000w'".to_string())
+        );
+        assert_eq!(
+            Query::parse("type:x").err(),
+            Some("invalid type 'x', use f, d or l".to_string())
+        );
+        assert_eq!(
+            Query::parse("depth:deep").err(),
+            Some("invalid depth 'deep'".to_string())
+        );
+    }
+
+    #[test]
+    fn sizes_and_durations_have_units() {
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("1.5k"), Some(1536));
+        assert_eq!(parse_size("2GB"), Some(2 << 30));
+        assert_eq!(parse_size("3X"), None);
+        assert_eq!(
+            parse_duration("2w"),
+            Some(Duration::from_secs(14 * 24 * 60 * 60))
+        );
+        assert_eq!(parse_duration("10"), None);
+    }
+}
@@ -0,0 +1,88 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+// what the file list is sorted by
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    #[default]
+    Name,
+    Size,
+    Date,
+}
+
+impl SortMode {
+    //the next mode when cycling with 'o'
+    pub fn next(self) -> SortMode {
+        match self {
+            SortMode::Name => SortMode::Size,
+            SortMode::Size => SortMode::Date,
+            SortMode::Date => SortMode::Name,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Name => "name",
+            SortMode::Size => "size",
+            SortMode::Date => "date",
+        }
+    }
+}
+
+// sort the entries in place, the biggest and newest come first when sorting by size or date
+pub fn sort_entries(entries: &mut [PathBuf], mode: SortMode, reverse: bool, dirs_first: bool) {
+    //read the metadata once instead of on every comparison
+    let mut keyed: Vec<(PathBuf, bool, u64, SystemTime)> = entries
+        .iter()
+        .map(|entry| {
+            let metadata = fs::metadata(entry).ok();
+            (
+                entry.clone(),
+                entry.is_dir(),
+                metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+                metadata
+                    .and_then(|m| m.modified().ok())
+                    .unwrap_or(SystemTime::UNIX_EPOCH),
+            )
+        })
+        .collect();
+
+    keyed.sort_by(|a, b| {
+        if dirs_first && a.1 != b.1 {
+            return b.1.cmp(&a.1);
+        }
+        let ordering = match mode {
+            SortMode::Name => compare_names(&a.0, &b.0),
+            SortMode::Size => b.2.cmp(&a.2).then_with(|| compare_names(&a.0, &b.0)),
+            SortMode::Date => b.3.cmp(&a.3).then_with(|| compare_names(&a.0, &b.0)),
+        };
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    for (slot, (entry, ..)) in entries.iter_mut().zip(keyed) {
+        *slot = entry;
+    }
+}
+
+fn compare_names(a: &Path, b: &Path) -> Ordering {
+    let a = a
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    let b = b
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    a.cmp(&b)
+}
@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::filter::{FilterCache, FilterMode};
+use crate::query::TreeCache;
+use crate::sort::SortMode;
+
+// how many directories the back history of a tab keeps
+const MAX_BACK_HISTORY: usize = 100;
+
+// the name of the selected entry of every visited directory, shared by all tabs
+pub type CursorMemory = HashMap<PathBuf, OsString>;
+
+// everything that belongs to one working directory, every tab has its own
+pub struct Tab {
+    //current directory
+    pub current_directory: PathBuf,
+    //the currently selected file
+    pub selected_file: usize,
+    //the query string when searching through the files
+    pub query: String,
+    //if the search bar is active
+    pub in_search: bool,
+    //how the query filters the entries
+    pub filter_mode: FilterMode,
+    //the regex or glob of the query, compiled once for all the frames it stays the same
+    pub filter_cache: FilterCache,
+    //the tree below the directory for a query with "depth:N"
+    pub tree_cache: TreeCache,
+    //what the entries are sorted by
+    pub sort_mode: SortMode,
+    //if the sort order is reversed
+    pub sort_reverse: bool,
+    //the entry to select once the entries of a new directory are listed
+    pub select_name: Option<OsString>,
+    //the entries picked for batch work, kept when changing the directory
+    pub selection: HashSet<PathBuf>,
+    //the first entry on the screen and how many fit, from the last time the list was drawn
+    pub list_offset: usize,
+    pub list_height: usize,
+    //the directories to go back to, the last one is the previous directory
+    back_stack: Vec<PathBuf>,
+    //the directories to go forward to after going back
+    forward_stack: Vec<PathBuf>,
+}
+
+impl Tab {
+    pub fn new(directory: PathBuf, config: &Config) -> Self {
+        Tab {
+            current_directory: directory,
+            selected_file: 0,
+            query: String::new(),
+            in_search: false,
+            filter_mode: FilterMode::default(),
+            filter_cache: FilterCache::default(),
+            tree_cache: TreeCache::new(),
+            sort_mode: config.sort,
+            sort_reverse: config.sort_reverse,
+            select_name: None,
+            selection: HashSet::new(),
+            list_offset: 0,
+            list_height: 0,
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+        }
+    }
+
+    //switch to a directory and record the old one in the back history
+    fn visit(&mut self, directory: PathBuf) {
+        if directory != self.current_directory {
+            let previous = std::mem::replace(&mut self.current_directory, directory);
+            self.back_stack.push(previous);
+            if self.back_stack.len() > MAX_BACK_HISTORY {
+                self.back_stack.remove(0);
+            }
+            self.forward_stack.clear();
+        }
+        self.selected_file = 0;
+        self.list_offset = 0;
+    }
+
+    //go to another directory, the cursor goes back to where it was the last time
+    pub fn change_directory(&mut self, directory: PathBuf, cursor_memory: &CursorMemory) {
+        self.select_name = cursor_memory.get(&directory).cloned();
+        self.visit(directory);
+    }
+
+    //go to the parent directory with the directory we came from selected
+    pub fn go_up(&mut self) {
+        if let Some(parent) = self.current_directory.parent() {
+            let parent = parent.to_path_buf();
+            self.select_name = self.current_directory.file_name().map(OsString::from);
+            self.visit(parent);
+        }
+    }
+
+    //go to the directory of a file with the file selected
+    pub fn reveal(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            self.select_name = path.file_name().map(OsString::from);
+            self.visit(parent.to_path_buf());
+        }
+    }
+
+    //go back in the history like in a browser
+    pub fn go_back(&mut self, cursor_memory: &CursorMemory) {
+        if let Some(directory) = self.back_stack.pop() {
+            let current = std::mem::replace(&mut self.current_directory, directory);
+            self.forward_stack.push(current);
+            self.select_name = cursor_memory.get(&self.current_directory).cloned();
+            self.selected_file = 0;
+            self.list_offset = 0;
+        }
+    }
+
+    //go forward again after going back
+    pub fn go_forward(&mut self, cursor_memory: &CursorMemory) {
+        if let Some(directory) = self.forward_stack.pop() {
+            let current = std::mem::replace(&mut self.current_directory, directory);
+            self.back_stack.push(current);
+            self.select_name = cursor_memory.get(&self.current_directory).cloned();
+            self.selected_file = 0;
+            self.list_offset = 0;
+        }
+    }
+
+    //move the selection and the screen by a number of lines like Ctrl-d and Ctrl-f in vim
+    pub fn scroll(&mut self, lines: usize, down: bool, len: usize) {
+        let last = len.saturating_sub(1);
+        if down {
+            self.selected_file = self.selected_file.saturating_add(lines).min(last);
+            self.list_offset = self
+                .list_offset
+                .saturating_add(lines)
+                .min(len.saturating_sub(self.list_height));
+        } else {
+            self.selected_file = self.selected_file.saturating_sub(lines);
+            self.list_offset = self.list_offset.saturating_sub(lines);
+        }
+    }
+
+    //select the entry in a row of the screen, the first row is 0
+    pub fn select_on_screen(&mut self, row: usize, len: usize) {
+        let last_row = self.list_height.saturating_sub(1);
+        self.selected_file = (self.list_offset + row.min(last_row)).min(len.saturating_sub(1));
+    }
+
+    //the directory we were in before this one, like "cd -"
+    pub fn previous_directory(&self) -> Option<PathBuf> {
+        self.back_stack.last().cloned()
+    }
+
+    //what a file operation works on, the selection or else the entry under the cursor
+    pub fn targets(&self, entries: &[PathBuf]) -> Vec<PathBuf> {
+        if self.selection.is_empty() {
+            return entries
+                .get(self.selected_file)
+                .cloned()
+                .into_iter()
+                .collect();
+        }
+        let mut targets: Vec<PathBuf> = self.selection.iter().cloned().collect();
+        targets.sort();
+        targets
+    }
+
+    //select or deselect one entry
+    pub fn toggle_selection(&mut self, entry: &Path) {
+        if !self.selection.remove(entry) {
+            self.selection.insert(entry.to_path_buf());
+        }
+    }
+
+    //add the entries to the selection, returns how many were not selected before
+    pub fn select(&mut self, entries: &[PathBuf]) -> usize {
+        entries
+            .iter()
+            .filter(|entry| self.selection.insert(entry.to_path_buf()))
+            .count()
+    }
+
+    //remove the entries from the selection, returns how many were selected before
+    pub fn deselect(&mut self, entries: &[PathBuf]) -> usize {
+        entries
+            .iter()
+            .filter(|entry| self.selection.remove(entry.as_path()))
+            .count()
+    }
+
+    //select the unselected entries and deselect the selected ones, returns how many got selected
+    pub fn invert_selection(&mut self, entries: &[PathBuf]) -> usize {
+        entries
+            .iter()
+            .filter(|entry| {
+                if self.selection.remove(entry.as_path()) {
+                    false
+                } else {
+                    self.selection.insert(entry.to_path_buf())
+                }
+            })
+            .count()
+    }
+
+    //the name of the directory for the tab bar
+    pub fn title(&self) -> String {
+        self.current_directory
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.current_directory.to_string_lossy().to_string())
+    }
+}